@@ -4,10 +4,11 @@ use crate::prelude::*;
 
 use crate::BenchmarkCommand;
 use crate::constants;
+use crate::hooks::HookKind;
+use crate::process::BenchmarkProcess;
 use crate::uri;
 use instrument_hooks_bindings::InstrumentHooks;
 use std::path::PathBuf;
-use std::process::Command;
 
 mod ld_preload_check;
 mod preload_lib_file;
@@ -16,19 +17,30 @@ pub fn perform(commands: Vec<BenchmarkCommand>) -> Result<()> {
     let hooks = InstrumentHooks::instance(INTEGRATION_NAME, INTEGRATION_VERSION);
 
     for benchmark_cmd in commands {
-        let name_and_uri = uri::generate_name_and_uri(&benchmark_cmd.name, &benchmark_cmd.command);
+        let name_and_uri = uri::generate_name_and_uri(
+            &benchmark_cmd.name,
+            &benchmark_cmd.command,
+            &benchmark_cmd.params,
+        );
         name_and_uri.print_executing();
 
-        let mut cmd = Command::new(&benchmark_cmd.command[0]);
-        cmd.args(&benchmark_cmd.command[1..]);
-        hooks.start_benchmark().unwrap();
-        let status = cmd.status();
-        hooks.stop_benchmark().unwrap();
-        let status = status.context("Failed to execute command")?;
+        let timeout = benchmark_cmd.parse_timeout()?;
+        benchmark_cmd.with_setup_and_teardown(|| {
+            benchmark_cmd.run_hook(HookKind::BeforeEach)?;
 
-        if !status.success() {
-            bail!("Command exited with non-zero status: {status}");
-        }
+            let mut cmd = benchmark_cmd.to_checked_process_command()?;
+            hooks.start_benchmark().unwrap();
+            let output = BenchmarkProcess::spawn(&mut cmd, timeout).and_then(|mut child| {
+                let stdout = child.read_stdout()?;
+                Ok((child.wait()?, stdout))
+            });
+            hooks.stop_benchmark().unwrap();
+            let (status, stdout) = output?;
+
+            benchmark_cmd.check_execution(status, stdout)?;
+
+            benchmark_cmd.run_hook(HookKind::AfterEach)
+        })?;
 
         hooks.set_executed_benchmark(&name_and_uri.uri).unwrap();
     }
@@ -48,26 +60,38 @@ pub fn perform_with_valgrind(commands: Vec<BenchmarkCommand>) -> Result<()> {
         // Check if the executable will honor LD_PRELOAD before running
         ld_preload_check::check_ld_preload_compatible(&benchmark_cmd.command[0])?;
 
-        let name_and_uri = uri::generate_name_and_uri(&benchmark_cmd.name, &benchmark_cmd.command);
+        let name_and_uri = uri::generate_name_and_uri(
+            &benchmark_cmd.name,
+            &benchmark_cmd.command,
+            &benchmark_cmd.params,
+        );
         name_and_uri.print_executing();
 
-        let mut cmd = Command::new(&benchmark_cmd.command[0]);
-        cmd.args(&benchmark_cmd.command[1..]);
-        // Use LD_PRELOAD to inject instrumentation into the child process
-        cmd.env("LD_PRELOAD", preload_lib_path);
-        // Make sure python processes output perf maps. This is usually done by `pytest-codspeed`
-        cmd.env("PYTHONPERFSUPPORT", "1");
-        cmd.env(constants::URI_ENV, &name_and_uri.uri);
+        let timeout = benchmark_cmd.parse_timeout()?;
+        benchmark_cmd.with_setup_and_teardown(|| {
+            benchmark_cmd.run_hook(HookKind::BeforeEach)?;
 
-        let mut child = cmd.spawn().context("Failed to spawn command")?;
+            let mut cmd = benchmark_cmd.to_checked_process_command()?;
+            // Use LD_PRELOAD to inject instrumentation into the child process
+            cmd.env("LD_PRELOAD", preload_lib_path);
+            // Make sure python processes output perf maps. This is usually done by `pytest-codspeed`
+            cmd.env("PYTHONPERFSUPPORT", "1");
+            cmd.env(constants::URI_ENV, &name_and_uri.uri);
 
-        let status = child.wait().context("Failed to execute command")?;
+            let mut child = BenchmarkProcess::spawn(&mut cmd, timeout)?;
+            let pid = child.id();
 
-        bail_if_command_spawned_subprocesses_under_valgrind(child.id())?;
+            let stdout = child.read_stdout()?;
+            let status = child.wait()?;
 
-        if !status.success() {
-            bail!("Command exited with non-zero status: {status}");
-        }
+            // Checked before the after-each hook runs, since its process would be detected as a
+            // subprocess of the benchmark
+            bail_if_command_spawned_subprocesses_under_valgrind(pid)?;
+
+            benchmark_cmd.check_execution(status, stdout)?;
+
+            benchmark_cmd.run_hook(HookKind::AfterEach)
+        })?;
     }
 
     Ok(())
@@ -0,0 +1,182 @@
+use crate::BenchmarkCommand;
+use crate::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::process::ExitStatus;
+
+/// Length of a hex encoded SHA-256 checksum
+const SHA256_HEX_LEN: usize = 64;
+
+/// Expectations on the executions of a benchmark, so that an early exit on an error path is not
+/// silently measured instead of the benchmarked work
+///
+/// ⚠️ Make sure to update BenchmarkAssertions::to_cli_args() when fields change, else the runner
+/// will not properly forward arguments
+#[derive(Debug, Clone, Default, PartialEq, clap::Args, Serialize, Deserialize)]
+pub struct BenchmarkAssertions {
+    /// Exit code expected from every execution of the command, for commands that legitimately
+    /// exit with a non-zero code (e.g., a linter finding issues).
+    ///
+    /// Default: 0
+    #[arg(long, value_name = "CODE")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_exit_code: Option<i32>,
+
+    /// Hex encoded SHA-256 checksum expected from the standard output of the first execution of
+    /// the command
+    #[arg(long, value_name = "SHA256")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_stdout_sha256: Option<String>,
+
+    /// Regex that the standard output of the first execution of the command must match
+    #[arg(long, value_name = "REGEX")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_stdout_regex: Option<String>,
+}
+
+impl BenchmarkAssertions {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Convert BenchmarkAssertions back to CLI argument strings
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if let Some(expect_exit_code) = &self.expect_exit_code {
+            args.push("--expect-exit-code".to_string());
+            args.push(expect_exit_code.to_string());
+        }
+
+        if let Some(expect_stdout_sha256) = &self.expect_stdout_sha256 {
+            args.push("--expect-stdout-sha256".to_string());
+            args.push(expect_stdout_sha256.clone());
+        }
+
+        if let Some(expect_stdout_regex) = &self.expect_stdout_regex {
+            args.push("--expect-stdout-regex".to_string());
+            args.push(expect_stdout_regex.clone());
+        }
+
+        args
+    }
+
+    /// Check the expected values before running the command
+    pub fn validate(&self) -> Result<()> {
+        if let Some(checksum) = &self.expect_stdout_sha256 {
+            if checksum.len() != SHA256_HEX_LEN || !checksum.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                bail!(
+                    "Invalid expect_stdout_sha256 `{checksum}`, expected {SHA256_HEX_LEN} hex characters"
+                );
+            }
+        }
+        if let Some(regex) = &self.expect_stdout_regex {
+            Regex::new(regex).with_context(|| format!("Invalid expect_stdout_regex `{regex}`"))?;
+        }
+        Ok(())
+    }
+
+    /// Whether the standard output of the first execution must be captured to be checked
+    pub fn checks_stdout(&self) -> bool {
+        self.expect_stdout_sha256.is_some() || self.expect_stdout_regex.is_some()
+    }
+
+    /// Check the exit status of an execution of the command
+    pub fn check_status(&self, status: ExitStatus) -> Result<()> {
+        match self.expect_exit_code {
+            None if !status.success() => {
+                bail!("Command exited with non-zero status: {status}")
+            }
+            Some(expected) if status.code() != Some(expected) => {
+                bail!("Command exited with {status}, expected exit code {expected}")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the standard output of the first execution of the command
+    pub fn check_stdout(&self, stdout: &[u8]) -> Result<()> {
+        if let Some(expected) = &self.expect_stdout_sha256 {
+            let checksum = sha256::digest(stdout);
+            if !checksum.eq_ignore_ascii_case(expected) {
+                bail!("Command output has SHA-256 checksum {checksum}, expected {expected}");
+            }
+        }
+        if let Some(regex) = &self.expect_stdout_regex {
+            let stdout = String::from_utf8_lossy(stdout);
+            if !Regex::new(regex)?.is_match(&stdout) {
+                bail!("Command output does not match the expected regex `{regex}`");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BenchmarkCommand {
+    /// Check the exit status of an execution of the command, and its standard output if it was
+    /// captured, which is then written to its configured target
+    pub fn check_execution(&self, status: ExitStatus, stdout: Option<Vec<u8>>) -> Result<()> {
+        if let Some(stdout) = &stdout {
+            self.write_captured_stdout(stdout)?;
+        }
+        self.assertions.check_status(status)?;
+        if let Some(stdout) = &stdout {
+            self.assertions.check_stdout(stdout)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    #[test]
+    fn test_check_status() {
+        let exit_code = |code: i32| ExitStatus::from_raw(code << 8);
+
+        let default = BenchmarkAssertions::default();
+        default.check_status(exit_code(0)).unwrap();
+        assert_eq!(
+            default.check_status(exit_code(1)).unwrap_err().to_string(),
+            "Command exited with non-zero status: exit status: 1"
+        );
+
+        let expect_one = BenchmarkAssertions {
+            expect_exit_code: Some(1),
+            ..Default::default()
+        };
+        expect_one.check_status(exit_code(1)).unwrap();
+        assert_eq!(
+            expect_one
+                .check_status(exit_code(0))
+                .unwrap_err()
+                .to_string(),
+            "Command exited with exit status: 0, expected exit code 1"
+        );
+    }
+
+    #[test]
+    fn test_check_stdout() {
+        let assertions = BenchmarkAssertions {
+            // SHA-256 of "hello\n"
+            expect_stdout_sha256: Some(
+                "5891B5B522D5DF086D0FF0B110FBD9D21BB4FC7163AF34D08286A2E846F6BE03".to_string(),
+            ),
+            expect_stdout_regex: Some("^hel+o".to_string()),
+            ..Default::default()
+        };
+        assertions.validate().unwrap();
+        assert!(assertions.checks_stdout());
+        assertions.check_stdout(b"hello\n").unwrap();
+        assert!(assertions.check_stdout(b"error\n").is_err());
+
+        let invalid = BenchmarkAssertions {
+            expect_stdout_sha256: Some("abc".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -13,3 +13,7 @@ pub const INTEGRATION_NAME: &str = env!("CODSPEED_INTEGRATION_NAME");
 /// Integration version reported to CodSpeed.
 /// This should match the version of the `codspeed` crate dependency.
 pub const INTEGRATION_VERSION: &str = env!("CODSPEED_INTEGRATION_VERSION");
+
+/// Executable timed to calibrate the process spawn overhead, resolved from `PATH` like the
+/// benchmarked commands.
+pub const NOOP_EXECUTABLE: &str = "true";
@@ -0,0 +1,188 @@
+use crate::prelude::*;
+use regex::Regex;
+
+/// Prefix marking a selection pattern as a regex rather than a glob
+const REGEX_PREFIX: &str = "re:";
+
+/// Arguments selecting the benchmarks to run by name
+///
+/// ⚠️ Make sure to update BenchmarkFilterArgs::to_cli_args() when fields change, else the runner
+/// will not properly forward arguments
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct BenchmarkFilterArgs {
+    /// Only run the benchmarks whose name matches one of the patterns, can be repeated.
+    /// Benchmarks without a name are matched on their command.
+    ///
+    /// Format: glob (e.g. "parse_*") or regex prefixed with "re:" (e.g. "re:^parse_(json|yaml)$")
+    #[arg(long = "bench", value_name = "PATTERN")]
+    pub bench: Vec<String>,
+
+    /// Skip the benchmarks whose name matches one of the patterns, can be repeated.
+    /// Takes precedence over --bench.
+    ///
+    /// Format: same as --bench
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+}
+
+impl BenchmarkFilterArgs {
+    pub fn is_empty(&self) -> bool {
+        self.bench.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        for pattern in &self.bench {
+            args.push("--bench".to_string());
+            args.push(pattern.clone());
+        }
+
+        for pattern in &self.exclude {
+            args.push("--exclude".to_string());
+            args.push(pattern.clone());
+        }
+
+        args
+    }
+}
+
+/// Compiled benchmark selection patterns
+#[derive(Debug)]
+pub struct BenchmarkFilter {
+    bench: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl TryFrom<&BenchmarkFilterArgs> for BenchmarkFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(args: &BenchmarkFilterArgs) -> Result<Self> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| compile_pattern(pattern))
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(Self {
+            bench: compile(&args.bench)?,
+            exclude: compile(&args.exclude)?,
+        })
+    }
+}
+
+impl BenchmarkFilter {
+    pub fn matches(&self, name: &str) -> bool {
+        let is_selected =
+            self.bench.is_empty() || self.bench.iter().any(|regex| regex.is_match(name));
+        is_selected && !self.exclude.iter().any(|regex| regex.is_match(name))
+    }
+}
+
+fn compile_pattern(pattern: &str) -> Result<Regex> {
+    let regex = match pattern.strip_prefix(REGEX_PREFIX) {
+        Some(regex) => regex.to_string(),
+        None => glob_to_regex(pattern),
+    };
+    Regex::new(&regex).with_context(|| format!("Invalid benchmark selection pattern: '{pattern}'"))
+}
+
+/// Translate a glob into an anchored regex, supporting `*`, `?` and `[...]` classes
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '[' => {
+                let class = chars.by_ref().take_while(|&c| c != ']').collect::<String>();
+                let class = class
+                    .strip_prefix('!')
+                    .map(|negated| format!("^{negated}"))
+                    .unwrap_or(class);
+                regex.push('[');
+                regex.push_str(&class.replace('\\', "\\\\"));
+                regex.push(']');
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_filter(bench: &[&str], exclude: &[&str]) -> BenchmarkFilter {
+        BenchmarkFilter::try_from(&BenchmarkFilterArgs {
+            bench: bench.iter().map(|p| p.to_string()).collect(),
+            exclude: exclude.iter().map(|p| p.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = build_filter(&[], &[]);
+        assert!(filter.matches("anything"));
+    }
+
+    #[test]
+    fn test_glob_patterns() {
+        let filter = build_filter(&["parse_*", "fib_?[0-9]"], &[]);
+        assert!(filter.matches("parse_json"));
+        assert!(filter.matches("fib_10"));
+        assert!(!filter.matches("fib_1"));
+        assert!(!filter.matches("my_parse_json"));
+
+        let filter = build_filter(&["./bench/*.sh"], &[]);
+        assert!(filter.matches("./bench/run.sh"));
+        assert!(!filter.matches("./bench/runXsh"));
+    }
+
+    #[test]
+    fn test_regex_patterns() {
+        let filter = build_filter(&["re:^parse_(json|yaml)$"], &[]);
+        assert!(filter.matches("parse_json"));
+        assert!(filter.matches("parse_yaml"));
+        assert!(!filter.matches("parse_toml"));
+    }
+
+    #[test]
+    fn test_exclude_takes_precedence() {
+        let filter = build_filter(&["parse_*"], &["*_slow"]);
+        assert!(filter.matches("parse_json"));
+        assert!(!filter.matches("parse_json_slow"));
+
+        let filter = build_filter(&[], &["re:slow"]);
+        assert!(filter.matches("fast"));
+        assert!(!filter.matches("very_slow_one"));
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let result = BenchmarkFilter::try_from(&BenchmarkFilterArgs {
+            bench: vec!["re:(".into()],
+            exclude: vec![],
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_cli_args() {
+        let args = BenchmarkFilterArgs {
+            bench: vec!["a*".into(), "b".into()],
+            exclude: vec!["c".into()],
+        };
+        assert_eq!(
+            args.to_cli_args(),
+            vec!["--bench", "a*", "--bench", "b", "--exclude", "c"]
+        );
+    }
+}
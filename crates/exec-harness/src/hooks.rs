@@ -0,0 +1,189 @@
+use crate::BenchmarkCommand;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::process::Command;
+
+/// Shell commands run around a benchmark, excluded from its measurement
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BenchmarkHooks {
+    /// Run once before the first execution of the benchmark
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<String>,
+
+    /// Run once after the last execution of the benchmark, even if it failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teardown: Option<String>,
+
+    /// Run before each round of executions of the benchmark
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before_each: Option<String>,
+
+    /// Run after each round of executions of the benchmark
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_each: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    Setup,
+    Teardown,
+    BeforeEach,
+    AfterEach,
+}
+
+impl Display for HookKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookKind::Setup => write!(f, "setup"),
+            HookKind::Teardown => write!(f, "teardown"),
+            HookKind::BeforeEach => write!(f, "before-each"),
+            HookKind::AfterEach => write!(f, "after-each"),
+        }
+    }
+}
+
+impl BenchmarkHooks {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn get(&self, kind: HookKind) -> Option<&str> {
+        match kind {
+            HookKind::Setup => self.setup.as_deref(),
+            HookKind::Teardown => self.teardown.as_deref(),
+            HookKind::BeforeEach => self.before_each.as_deref(),
+            HookKind::AfterEach => self.after_each.as_deref(),
+        }
+    }
+}
+
+impl BenchmarkCommand {
+    /// Run a hook of the benchmark with `sh -c`, in the environment and working directory of the
+    /// benchmark. Does nothing if the hook is not defined.
+    pub fn run_hook(&self, kind: HookKind) -> Result<()> {
+        let Some(script) = self.hooks.get(kind) else {
+            return Ok(());
+        };
+
+        debug!("Running {kind} hook: {script}");
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        let status = self
+            .in_environment(cmd)
+            .status()
+            .with_context(|| format!("Failed to execute the {kind} hook `{script}`"))?;
+
+        if !status.success() {
+            bail!("The {kind} hook `{script}` exited with non-zero status: {status}");
+        }
+
+        Ok(())
+    }
+
+    /// Run `f` between the setup and teardown hooks, the teardown hook is run even if `f` fails
+    pub fn with_setup_and_teardown<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        self.run_hook(HookKind::Setup)?;
+        let result = f();
+        let teardown_result = self.run_hook(HookKind::Teardown);
+
+        let value = result?;
+        teardown_result?;
+        Ok(value)
+    }
+}
+
+/// Run `f` between the setup hooks of all `commands`, in order, and their teardown hooks, in
+/// reverse order. Only the teardown hooks of the commands whose setup hook ran are run.
+pub fn with_setups_and_teardowns<T>(
+    commands: &[BenchmarkCommand],
+    f: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    match commands.split_first() {
+        Some((first, rest)) => first.with_setup_and_teardown(|| with_setups_and_teardowns(rest, f)),
+        None => f(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn command_with_hooks(hooks: BenchmarkHooks) -> BenchmarkCommand {
+        BenchmarkCommand {
+            command: vec!["true".to_string()],
+            hooks,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_undefined_hook_is_a_noop() {
+        let command = command_with_hooks(BenchmarkHooks::default());
+        command.run_hook(HookKind::Setup).unwrap();
+    }
+
+    #[test]
+    fn test_hook_runs_in_benchmark_environment() {
+        let tmpdir = TempDir::new().unwrap();
+        let command = BenchmarkCommand {
+            env: [("FIXTURE".to_string(), "db.sqlite".to_string())].into(),
+            working_directory: Some(tmpdir.path().to_path_buf()),
+            ..command_with_hooks(BenchmarkHooks {
+                setup: Some("touch \"$FIXTURE\"".to_string()),
+                ..Default::default()
+            })
+        };
+
+        command.run_hook(HookKind::Setup).unwrap();
+        assert!(tmpdir.path().join("db.sqlite").exists());
+    }
+
+    #[test]
+    fn test_failing_hook_is_reported() {
+        let command = command_with_hooks(BenchmarkHooks {
+            before_each: Some("exit 3".to_string()),
+            ..Default::default()
+        });
+
+        let error = command.run_hook(HookKind::BeforeEach).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("The before-each hook `exit 3`"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_teardown_runs_after_failure() {
+        let tmpdir = TempDir::new().unwrap();
+        let marker = tmpdir.path().join("teardown");
+        let command = command_with_hooks(BenchmarkHooks {
+            teardown: Some(format!("touch {}", marker.display())),
+            ..Default::default()
+        });
+
+        let result =
+            command.with_setup_and_teardown(|| -> Result<()> { bail!("benchmark failed") });
+        assert_eq!(result.unwrap_err().to_string(), "benchmark failed");
+        assert!(marker.exists());
+    }
+
+    #[test]
+    fn test_failing_setup_skips_benchmark() {
+        let command = command_with_hooks(BenchmarkHooks {
+            setup: Some("false".to_string()),
+            ..Default::default()
+        });
+
+        let mut ran = false;
+        let result = command.with_setup_and_teardown(|| {
+            ran = true;
+            Ok(())
+        });
+        assert!(result.is_err());
+        assert!(!ran);
+    }
+}
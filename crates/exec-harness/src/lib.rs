@@ -1,11 +1,20 @@
 use clap::ValueEnum;
 use prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::io::{self, BufRead};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::Duration;
 
 pub mod analysis;
+pub mod assertions;
 pub mod constants;
+pub mod filter;
+pub mod hooks;
 pub mod prelude;
+pub mod process;
+pub mod stdio;
 mod uri;
 pub mod walltime;
 
@@ -23,7 +32,7 @@ pub enum MeasurementMode {
 /// This struct defines the JSON format for passing benchmark commands to exec-harness
 /// via stdin (when invoked with `-`). The runner uses this same struct to serialize
 /// targets from codspeed.yaml.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BenchmarkCommand {
     /// The command and arguments to execute
     pub command: Vec<String>,
@@ -32,9 +41,97 @@ pub struct BenchmarkCommand {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
+    /// Parameters of the benchmark, encoded in its name and URI
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, String>,
+
     /// Walltime execution options (flattened into the JSON object)
     #[serde(default)]
     pub walltime_args: walltime::WalltimeExecutionArgs,
+
+    /// Environment variables set for the command, on top of the inherited environment
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+
+    /// Directory to run the command from, defaults to the current directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<PathBuf>,
+
+    /// Maximum duration of a single execution of the command, after which it is killed.
+    ///
+    /// Format: duration string (e.g., "30s", "500ms", "2m") or number in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+
+    /// Commands run around the benchmark, outside of its measurement
+    #[serde(default, skip_serializing_if = "hooks::BenchmarkHooks::is_empty")]
+    pub hooks: hooks::BenchmarkHooks,
+
+    /// Expected exit code and output of the command
+    #[serde(
+        default,
+        skip_serializing_if = "assertions::BenchmarkAssertions::is_empty"
+    )]
+    pub assertions: assertions::BenchmarkAssertions,
+
+    /// Redirections of the standard streams of the command
+    #[serde(default, skip_serializing_if = "stdio::BenchmarkStdio::is_empty")]
+    pub stdio: stdio::BenchmarkStdio,
+}
+
+impl BenchmarkCommand {
+    /// Build the process executing the command, in its environment and working directory, with
+    /// its standard streams redirected
+    pub fn to_process_command(&self) -> Result<Command> {
+        let mut cmd = Command::new(&self.command[0]);
+        cmd.args(&self.command[1..]);
+        let mut cmd = self.in_environment(cmd);
+        self.redirect_stdio(&mut cmd)?;
+        Ok(cmd)
+    }
+
+    /// Build the process executing the command like [`BenchmarkCommand::to_process_command`],
+    /// with its standard output piped if it must be checked by the assertions
+    pub fn to_checked_process_command(&self) -> Result<Command> {
+        let mut cmd = self.to_process_command()?;
+        if self.assertions.checks_stdout() {
+            cmd.stdout(Stdio::piped());
+        }
+        Ok(cmd)
+    }
+
+    /// Build a process executing a no-op executable the same way as the command, to calibrate
+    /// the process spawn overhead
+    pub fn to_noop_process_command(&self) -> Command {
+        self.in_environment(Command::new(constants::NOOP_EXECUTABLE))
+    }
+
+    fn in_environment(&self, mut cmd: Command) -> Command {
+        cmd.envs(&self.env);
+        if let Some(working_directory) = &self.working_directory {
+            cmd.current_dir(working_directory);
+        }
+        cmd
+    }
+
+    pub fn parse_timeout(&self) -> Result<Option<Duration>> {
+        let Some(timeout) = &self.timeout else {
+            return Ok(None);
+        };
+
+        let timeout_ns = walltime::parse_duration_to_ns(timeout)?;
+        if timeout_ns == 0 {
+            bail!("The timeout must be greater than 0");
+        }
+        Ok(Some(Duration::from_nanos(timeout_ns)))
+    }
+}
+
+/// Folder where the results are written, set by the runner
+pub(crate) fn profile_folder() -> PathBuf {
+    std::env::var("CODSPEED_PROFILE_FOLDER")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap().join(".codspeed"))
 }
 
 /// Read and parse benchmark commands from stdin as JSON
@@ -64,14 +161,46 @@ pub fn read_commands_from_stdin() -> Result<Vec<BenchmarkCommand>> {
     Ok(commands)
 }
 
+/// Keep the benchmark commands selected by the `--bench` and `--exclude` patterns
+pub fn filter_commands(
+    commands: Vec<BenchmarkCommand>,
+    filter_args: &filter::BenchmarkFilterArgs,
+) -> Result<Vec<BenchmarkCommand>> {
+    if filter_args.is_empty() {
+        return Ok(commands);
+    }
+
+    let filter = filter::BenchmarkFilter::try_from(filter_args)?;
+    let commands = commands
+        .into_iter()
+        .filter(|cmd| {
+            let name = cmd.name.clone().unwrap_or_else(|| cmd.command.join(" "));
+            filter.matches(&name)
+        })
+        .collect::<Vec<_>>();
+
+    if commands.is_empty() {
+        bail!("No benchmark matches the --bench and --exclude patterns");
+    }
+
+    Ok(commands)
+}
+
 /// Execute benchmark commands
 pub fn execute_benchmarks(
     commands: Vec<BenchmarkCommand>,
     measurement_mode: Option<MeasurementMode>,
+    scheduling: &walltime::SchedulingArgs,
 ) -> Result<()> {
+    for cmd in &commands {
+        cmd.assertions
+            .validate()
+            .with_context(|| format!("Invalid assertions of `{}`", cmd.command.join(" ")))?;
+    }
+
     match measurement_mode {
         Some(MeasurementMode::Walltime) | None => {
-            walltime::perform(commands)?;
+            walltime::perform(commands, scheduling)?;
         }
         Some(MeasurementMode::Memory) => {
             analysis::perform(commands)?;
@@ -1,8 +1,12 @@
 use clap::Parser;
+use exec_harness::assertions::BenchmarkAssertions;
+use exec_harness::filter::BenchmarkFilterArgs;
 use exec_harness::prelude::*;
-use exec_harness::walltime::WalltimeExecutionArgs;
+use exec_harness::stdio::BenchmarkStdio;
+use exec_harness::walltime::{SchedulingArgs, WalltimeExecutionArgs};
 use exec_harness::{
-    BenchmarkCommand, MeasurementMode, execute_benchmarks, read_commands_from_stdin,
+    BenchmarkCommand, MeasurementMode, execute_benchmarks, filter_commands,
+    read_commands_from_stdin,
 };
 
 #[derive(Parser, Debug)]
@@ -23,6 +27,20 @@ struct Args {
     #[command(flatten)]
     walltime_args: WalltimeExecutionArgs,
 
+    #[command(flatten)]
+    filter_args: BenchmarkFilterArgs,
+
+    /// Only applies to a single command, the benchmarks read from stdin define their own
+    #[command(flatten)]
+    assertions: BenchmarkAssertions,
+
+    /// Only applies to a single command, the benchmarks read from stdin define their own
+    #[command(flatten)]
+    stdio: BenchmarkStdio,
+
+    #[command(flatten)]
+    scheduling_args: SchedulingArgs,
+
     /// The command and arguments to execute.
     /// Use "-" as the only argument to read a JSON payload from stdin.
     #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
@@ -51,10 +69,14 @@ fn main() -> Result<()> {
             command: args.command,
             name: args.name,
             walltime_args: args.walltime_args,
+            assertions: args.assertions,
+            stdio: args.stdio,
+            ..Default::default()
         }],
     };
+    let commands = filter_commands(commands, &args.filter_args)?;
 
-    execute_benchmarks(commands, measurement_mode)?;
+    execute_benchmarks(commands, measurement_mode, &args.scheduling_args)?;
 
     Ok(())
 }
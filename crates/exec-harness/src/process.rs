@@ -0,0 +1,225 @@
+use crate::prelude::*;
+use runner_shared::walltime_results::ResourceUsage;
+use std::io::{self, Read};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Child, Command, ExitStatus};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// A spawned benchmark process, killed if it runs longer than its timeout
+pub struct BenchmarkProcess {
+    child: Child,
+    watchdog: Option<Watchdog>,
+}
+
+struct Watchdog {
+    timeout: Duration,
+    stop: Sender<()>,
+    /// Returns whether the process was killed
+    handle: JoinHandle<bool>,
+}
+
+impl BenchmarkProcess {
+    pub fn spawn(command: &mut Command, timeout: Option<Duration>) -> Result<Self> {
+        if timeout.is_some() {
+            // Lead a process group, so that the children of the process, e.g. those of a shell,
+            // are killed along with it and don't keep its output open
+            command.process_group(0);
+        }
+        let child = command.spawn().context("Failed to execute command")?;
+        let watchdog = timeout.map(|timeout| {
+            let pid = child.id() as libc::pid_t;
+            let (stop, stopped) = mpsc::channel();
+            let handle = thread::spawn(move || {
+                if stopped.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                    // SAFETY: the process is not reaped before the watchdog is stopped, so the pid
+                    // still refers to it and to its process group, even once it exited
+                    unsafe { libc::kill(-pid, libc::SIGKILL) };
+                    true
+                } else {
+                    false
+                }
+            });
+            Watchdog {
+                timeout,
+                stop,
+                handle,
+            }
+        });
+
+        Ok(Self { child, watchdog })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Read the standard output of the process until it is closed. Returns `None` if the process
+    /// was not spawned with a piped stdout.
+    pub fn read_stdout(&mut self) -> Result<Option<Vec<u8>>> {
+        let Some(mut stdout) = self.child.stdout.take() else {
+            return Ok(None);
+        };
+
+        let mut output = Vec::new();
+        stdout
+            .read_to_end(&mut output)
+            .context("Failed to read the command output")?;
+        Ok(Some(output))
+    }
+
+    /// Wait for the process to exit, failing if it was killed for exceeding its timeout
+    pub fn wait(self) -> Result<ExitStatus> {
+        self.wait_with_usage().map(|(status, _)| status)
+    }
+
+    /// Wait for the process to exit like [`BenchmarkProcess::wait`], and return the resources
+    /// it used along with its exit status
+    pub fn wait_with_usage(mut self) -> Result<(ExitStatus, ResourceUsage)> {
+        let Some(watchdog) = self.watchdog.take() else {
+            return reap(self.child.id()).context("Failed to wait for command to finish");
+        };
+
+        // Only reap the process once the watchdog cannot kill it anymore, to prevent its pid
+        // from being reused in between
+        wait_without_reaping(self.child.id()).context("Failed to wait for command to finish")?;
+        let _ = watchdog.stop.send(());
+        let killed = watchdog.handle.join().unwrap_or(false);
+        let (status, usage) =
+            reap(self.child.id()).context("Failed to wait for command to finish")?;
+
+        if killed {
+            bail!(
+                "Command was killed after exceeding its timeout of {:?}",
+                watchdog.timeout
+            );
+        }
+        Ok((status, usage))
+    }
+}
+
+/// Reap the process with `wait4`, which unlike [`Child::wait`] reports its resource usage
+fn reap(pid: u32) -> io::Result<(ExitStatus, ResourceUsage)> {
+    loop {
+        let mut status = 0;
+        // SAFETY: rusage is a plain C struct for which zeroed memory is valid
+        let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+        // SAFETY: wait4 only writes to the provided status and rusage
+        let ret = unsafe { libc::wait4(pid as libc::pid_t, &mut status, 0, &mut rusage) };
+        if ret >= 0 {
+            return Ok((ExitStatus::from_raw(status), resource_usage(&rusage)));
+        }
+
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+fn resource_usage(rusage: &libc::rusage) -> ResourceUsage {
+    let timeval_ns = |time: libc::timeval| time.tv_sec as f64 * 1e9 + time.tv_usec as f64 * 1e3;
+    ResourceUsage {
+        user_time_ns: timeval_ns(rusage.ru_utime),
+        system_time_ns: timeval_ns(rusage.ru_stime),
+        // Reported in kilobytes on Linux
+        max_rss_bytes: rusage.ru_maxrss as u64 * 1024,
+        minor_faults: rusage.ru_minflt as f64,
+        major_faults: rusage.ru_majflt as f64,
+        voluntary_context_switches: rusage.ru_nvcsw as f64,
+        involuntary_context_switches: rusage.ru_nivcsw as f64,
+    }
+}
+
+fn wait_without_reaping(pid: u32) -> io::Result<()> {
+    loop {
+        // SAFETY: siginfo_t is a plain C struct for which zeroed memory is valid
+        let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+        // SAFETY: waitid only writes to the provided siginfo_t
+        let ret = unsafe {
+            libc::waitid(
+                libc::P_PID,
+                pid as libc::id_t,
+                &mut info,
+                libc::WEXITED | libc::WNOWAIT,
+            )
+        };
+        if ret == 0 {
+            return Ok(());
+        }
+
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Stdio;
+    use std::time::Instant;
+
+    #[test]
+    fn test_wait_without_timeout() {
+        let process = BenchmarkProcess::spawn(&mut Command::new("true"), None).unwrap();
+        assert!(process.wait().unwrap().success());
+    }
+
+    #[test]
+    fn test_wait_with_usage() {
+        let process =
+            BenchmarkProcess::spawn(&mut Command::new("true"), Some(Duration::from_secs(10)))
+                .unwrap();
+
+        let (status, usage) = process.wait_with_usage().unwrap();
+        assert!(status.success());
+        assert!(usage.max_rss_bytes > 0);
+        assert!(usage.minor_faults > 0.);
+    }
+
+    #[test]
+    fn test_process_exits_before_timeout() {
+        let process = BenchmarkProcess::spawn(
+            Command::new("sleep").arg("0.01"),
+            Some(Duration::from_secs(10)),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        assert!(process.wait().unwrap().success());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_process_killed_after_timeout() {
+        let process = BenchmarkProcess::spawn(
+            Command::new("sleep").arg("10"),
+            Some(Duration::from_millis(50)),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        let error = process.wait().unwrap_err();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(error.to_string().contains("timeout"), "{error}");
+    }
+
+    #[test]
+    fn test_process_children_killed_after_timeout() {
+        let mut command = Command::new("sh");
+        command
+            .args(["-c", "sleep 10 & sleep 10"])
+            .stdout(Stdio::piped());
+        let mut process =
+            BenchmarkProcess::spawn(&mut command, Some(Duration::from_millis(50))).unwrap();
+
+        // The output is only closed once the background sleep is killed as well
+        let start = Instant::now();
+        process.read_stdout().unwrap();
+        assert!(process.wait().is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::BenchmarkCommand;
+use crate::prelude::*;
+use crate::uri;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Folder of the profile folder where the outputs of the commands are written
+const OUTPUTS_DIR: &str = "outputs";
+
+/// Maximum length of the name of an output file, without its extension
+const MAX_OUTPUT_FILE_STEM_LENGTH: usize = 200;
+
+/// Standard streams of the executions of a benchmark, inherited from the harness by default
+///
+/// ⚠️ Make sure to update BenchmarkStdio::to_cli_args() when fields change, else the runner
+/// will not properly forward arguments
+#[derive(Debug, Clone, Default, PartialEq, clap::Args, Serialize, Deserialize)]
+pub struct BenchmarkStdio {
+    /// File fed to the standard input of each execution of the command
+    #[arg(long, value_name = "FILE")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdin: Option<PathBuf>,
+
+    /// Where the standard output of the command is written, `file` writing the output of the last
+    /// execution to `outputs/<benchmark name>.stdout` in the profile folder
+    ///
+    /// Default: inherit
+    #[arg(long, value_enum, value_name = "TARGET")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<OutputTarget>,
+
+    /// Where the standard error of the command is written, like --stdout
+    ///
+    /// Default: inherit
+    #[arg(long, value_enum, value_name = "TARGET")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<OutputTarget>,
+}
+
+/// Destination of an output stream of the command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputTarget {
+    /// Write to the output of the harness
+    Inherit,
+    /// Discard the output, to keep the throughput of the terminal or CI logs out of the measures
+    Null,
+    /// Write to a file in the profile folder
+    File,
+}
+
+impl BenchmarkStdio {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Convert BenchmarkStdio back to CLI argument strings
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if let Some(stdin) = &self.stdin {
+            args.push("--stdin".to_string());
+            args.push(stdin.to_string_lossy().into_owned());
+        }
+
+        for (flag, target) in [("--stdout", &self.stdout), ("--stderr", &self.stderr)] {
+            if let Some(target) = target {
+                args.push(flag.to_string());
+                args.push(
+                    target
+                        .to_possible_value()
+                        .expect("no skipped variant")
+                        .get_name()
+                        .to_string(),
+                );
+            }
+        }
+
+        args
+    }
+}
+
+impl BenchmarkCommand {
+    /// Connect the standard streams of the process to the configured files
+    pub(crate) fn redirect_stdio(&self, cmd: &mut Command) -> Result<()> {
+        if let Some(stdin) = &self.stdio.stdin {
+            let file = File::open(stdin)
+                .with_context(|| format!("Failed to open the stdin file {}", stdin.display()))?;
+            cmd.stdin(file);
+        }
+        if let Some(stdout) = self.stdio.stdout {
+            cmd.stdout(self.output_stdio(stdout, "stdout")?);
+        }
+        if let Some(stderr) = self.stdio.stderr {
+            cmd.stderr(self.output_stdio(stderr, "stderr")?);
+        }
+        Ok(())
+    }
+
+    /// Write the standard output captured from an execution to its configured target
+    pub(crate) fn write_captured_stdout(&self, output: &[u8]) -> Result<()> {
+        match self.stdio.stdout.unwrap_or(OutputTarget::Inherit) {
+            OutputTarget::Inherit => io::stdout().write_all(output)?,
+            OutputTarget::Null => {}
+            OutputTarget::File => {
+                let path = self.output_path("stdout")?;
+                fs::write(&path, output)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn output_stdio(&self, target: OutputTarget, stream: &str) -> Result<Stdio> {
+        Ok(match target {
+            OutputTarget::Inherit => Stdio::inherit(),
+            OutputTarget::Null => Stdio::null(),
+            OutputTarget::File => {
+                let path = self.output_path(stream)?;
+                File::create(&path)
+                    .with_context(|| format!("Failed to create {}", path.display()))?
+                    .into()
+            }
+        })
+    }
+
+    /// Path of the file of an output stream, named after the benchmark
+    pub(crate) fn output_path(&self, stream: &str) -> Result<PathBuf> {
+        let outputs_dir = crate::profile_folder().join(OUTPUTS_DIR);
+        fs::create_dir_all(&outputs_dir)
+            .with_context(|| format!("Failed to create {}", outputs_dir.display()))?;
+
+        let stem = uri::full_name(&self.name, &self.command, &self.params)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || "-_.[]=,".contains(c) {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        // Commands such as `./bench` would otherwise give hidden files
+        let mut stem = stem.trim_start_matches('.').to_string();
+        stem.truncate(MAX_OUTPUT_FILE_STEM_LENGTH);
+        Ok(outputs_dir.join(format!("{stem}.{stream}")))
+    }
+}
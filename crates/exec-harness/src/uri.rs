@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::collections::BTreeMap;
 
 pub struct NameAndUri {
     pub(crate) name: String,
@@ -10,8 +11,13 @@ pub struct NameAndUri {
 /// Should be removed once we have structured metadata around benchmarks
 const MAX_NAME_LENGTH: usize = 1024 - 100;
 
-pub fn generate_name_and_uri(name: &Option<String>, command: &[String]) -> NameAndUri {
-    let mut name = name.clone().unwrap_or_else(|| command.join(" "));
+/// Generate the name and URI of a benchmark, encoding its parameters as `name[key=value,...]`
+pub fn generate_name_and_uri(
+    name: &Option<String>,
+    command: &[String],
+    params: &BTreeMap<String, String>,
+) -> NameAndUri {
+    let mut name = full_name(name, command, params);
     let uri = format!("exec_harness::{name}");
 
     if name.len() > MAX_NAME_LENGTH {
@@ -36,3 +42,44 @@ impl NameAndUri {
         debug!("Command: {:?}", self.command);
     }
 }
+
+/// Name of a benchmark with its parameters, before it is truncated
+pub(crate) fn full_name(
+    name: &Option<String>,
+    command: &[String],
+    params: &BTreeMap<String, String>,
+) -> String {
+    let name = name.clone().unwrap_or_else(|| command.join(" "));
+    if params.is_empty() {
+        return name;
+    }
+    let params = params
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{name}[{params}]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_name_and_uri() {
+        let command = vec!["./bench".to_string(), "--fast".to_string()];
+
+        let name_and_uri = generate_name_and_uri(&None, &command, &BTreeMap::new());
+        assert_eq!(name_and_uri.name, "./bench --fast");
+        assert_eq!(name_and_uri.uri, "exec_harness::./bench --fast");
+
+        let params = [
+            ("threads".to_string(), "8".to_string()),
+            ("size".to_string(), "1k".to_string()),
+        ]
+        .into();
+        let name_and_uri = generate_name_and_uri(&Some("parse".to_string()), &command, &params);
+        assert_eq!(name_and_uri.name, "parse[size=1k,threads=8]");
+        assert_eq!(name_and_uri.uri, "exec_harness::parse[size=1k,threads=8]");
+    }
+}
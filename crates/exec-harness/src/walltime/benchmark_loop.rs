@@ -1,177 +1,594 @@
 use super::ExecutionOptions;
-use super::config::RoundOrTime;
+use super::config::{ItersPerRound, RoundOrTime, SpawnOverheadMode};
+use super::process_settings::{apply_process_settings, resolve_permitted_settings};
+use crate::BenchmarkCommand;
 use crate::constants::{INTEGRATION_NAME, INTEGRATION_VERSION};
+use crate::hooks::HookKind;
 use crate::prelude::*;
+use crate::process::BenchmarkProcess;
 use instrument_hooks_bindings::InstrumentHooks;
-use std::process::Command;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use runner_shared::walltime_results::{
+    ProcessSettings, ResourceUsage, SpawnOverhead, relative_precision,
+};
 use std::time::Duration;
 
+/// Minimum duration of a round when the iterations per round are chosen during warmup with
+/// `auto`, shorter commands are executed several times per round to average out the process
+/// spawn noise
+const MIN_ROUND_TIME_NS: u64 = 1_000_000; // 1ms
+
+/// Number of executions of the no-op executable timed to calibrate the process spawn overhead
+const SPAWN_OVERHEAD_CALIBRATION_ITERS: usize = 50;
+
+/// Minimum number of rounds before the precision of the mean is trusted to stop the rounds
+const MIN_PRECISION_ROUNDS: u64 = 5;
+
+/// Measurements of the rounds of a benchmark
+#[derive(Debug)]
+pub struct MeasuredRounds {
+    pub times_per_round_ns: Vec<u128>,
+    /// Number of executions of the command in each round
+    pub iters_per_round: u64,
+    /// Number of executions of the command during warmup
+    pub warmup_iters: u64,
+    /// Process spawn overhead, when calibrated
+    pub spawn_overhead: Option<SpawnOverhead>,
+    /// Resources used by the measured executions
+    pub resource_usage: Option<ResourceUsage>,
+    /// Settings the executions ran with, when not the default ones
+    pub process_settings: Option<ProcessSettings>,
+}
+
+/// Mean and variance of the time per iteration of the rounds, updated as they run with Welford's
+/// algorithm to check the precision of the mean without keeping the rounds in memory
+#[derive(Default)]
+struct RunningStats {
+    rounds: u64,
+    mean: f64,
+    sum_sq_diff: f64,
+}
+
+impl RunningStats {
+    fn push(&mut self, time_per_iter_ns: f64) {
+        self.rounds += 1;
+        let diff = time_per_iter_ns - self.mean;
+        self.mean += diff / self.rounds as f64;
+        self.sum_sq_diff += diff * (time_per_iter_ns - self.mean);
+    }
+
+    fn relative_precision(&self) -> Option<f64> {
+        if self.rounds < 2 {
+            return None;
+        }
+        let stdev = (self.sum_sq_diff / (self.rounds - 1) as f64).sqrt();
+        relative_precision(self.mean, stdev, self.rounds)
+    }
+}
+
+/// Timestamps and resource usage of one execution of the command
+struct Execution {
+    start_ts_ns: u64,
+    end_ts_ns: u64,
+    usage: ResourceUsage,
+}
+
+/// Run the rounds of a benchmark until its stop conditions are met
 pub fn run_rounds(
     bench_uri: String,
-    command: Vec<String>,
+    command: &BenchmarkCommand,
     config: &ExecutionOptions,
-) -> Result<Vec<u128>> {
-    let warmup_time_ns = config.warmup_time_ns;
+) -> Result<MeasuredRounds> {
     let hooks = InstrumentHooks::instance(INTEGRATION_NAME, INTEGRATION_VERSION);
+    let mut rounds = BenchmarkRounds::new(bench_uri, command, config)?;
 
-    let do_one_round = || -> Result<(u64, u64)> {
-        let mut child = Command::new(&command[0])
-            .args(&command[1..])
-            .spawn()
-            .context("Failed to execute command")?;
-        let bench_round_start_ts_ns = InstrumentHooks::current_timestamp();
-        let status = child
-            .wait()
-            .context("Failed to wait for command to finish")?;
+    hooks.start_benchmark().unwrap();
+    rounds.warmup()?;
+    while !rounds.is_finished() {
+        rounds.run_round()?;
+    }
+    // Only record the markers once all rounds ran, in order to avoid overhead during the loop
+    rounds.record_markers();
+    hooks.stop_benchmark().unwrap();
+    hooks.set_executed_benchmark(rounds.uri()).unwrap();
 
-        let bench_round_end_ts_ns = InstrumentHooks::current_timestamp();
+    Ok(rounds.finish())
+}
 
-        if !status.success() {
-            bail!("Command exited with non-zero status: {status}");
+/// Run the rounds of several benchmarks interleaved, so that drifts of the machine performance
+/// over time, e.g. thermal throttling, are spread across all of them instead of biasing the last
+/// ones. Every benchmark is warmed up first, then each pass runs one round of every unfinished
+/// benchmark, in an order shuffled with `shuffle_seed` if given.
+///
+/// Each warmup and round is measured in its own started benchmark followed by its URI, so that
+/// its markers are attributed to the right benchmark.
+pub fn run_interleaved_rounds(
+    mut benchmarks: Vec<BenchmarkRounds>,
+    shuffle_seed: Option<u64>,
+) -> Result<Vec<MeasuredRounds>> {
+    let hooks = InstrumentHooks::instance(INTEGRATION_NAME, INTEGRATION_VERSION);
+    let mut rng = shuffle_seed.map(StdRng::seed_from_u64);
+
+    for rounds in &mut benchmarks {
+        hooks.start_benchmark().unwrap();
+        rounds.warmup()?;
+        rounds.record_markers();
+        hooks.stop_benchmark().unwrap();
+        hooks.set_executed_benchmark(rounds.uri()).unwrap();
+    }
+
+    loop {
+        let mut order = (0..benchmarks.len())
+            .filter(|&i| !benchmarks[i].is_finished())
+            .collect::<Vec<_>>();
+        if order.is_empty() {
+            break;
+        }
+        if let Some(rng) = &mut rng {
+            order.shuffle(rng);
         }
 
-        Ok((bench_round_start_ts_ns, bench_round_end_ts_ns))
-    };
+        for i in order {
+            let rounds = &mut benchmarks[i];
+            hooks.start_benchmark().unwrap();
+            rounds.run_round()?;
+            rounds.record_markers();
+            hooks.stop_benchmark().unwrap();
+            hooks.set_executed_benchmark(rounds.uri()).unwrap();
+        }
+    }
 
-    // Compute the number of rounds to perform (potentially undefined if no warmup and only time constraints)
-    hooks.start_benchmark().unwrap();
-    let rounds_to_perform: Option<u64> = if warmup_time_ns > 0 {
-        match compute_rounds_from_warmup(config, do_one_round)? {
-            WarmupResult::EarlyReturn { start, end } => {
-                // Add marker for the single warmup round so the run still gets profiling data
-                hooks.add_benchmark_timestamps(start, end);
-                hooks.stop_benchmark().unwrap();
-                hooks.set_executed_benchmark(&bench_uri).unwrap();
-                return Ok(vec![(end - start) as u128]);
-            }
-            WarmupResult::Rounds(rounds) => Some(rounds),
+    Ok(benchmarks
+        .into_iter()
+        .map(BenchmarkRounds::finish)
+        .collect())
+}
+
+/// A benchmark being measured, whose rounds are run one at a time so that they can be
+/// interleaved with the rounds of other benchmarks
+pub struct BenchmarkRounds<'a> {
+    bench_uri: String,
+    command: &'a BenchmarkCommand,
+    config: &'a ExecutionOptions,
+    timeout: Option<Duration>,
+    hooks: &'static InstrumentHooks,
+    spawn_overhead: Option<SpawnOverhead>,
+    /// Settings applied to each execution, without the ones that are not permitted
+    process_settings: ProcessSettings,
+
+    /// Number of rounds to perform, potentially undefined if no warmup and only time constraints
+    rounds_to_perform: Option<u64>,
+    iters_per_round: u64,
+    warmup_iters: u64,
+    min_precision_rounds: u64,
+    min_time_ns: Option<u64>,
+    max_time_ns: Option<u64>,
+
+    current_round: u64,
+    /// Time spent in the rounds of this benchmark, excluding the rounds of interleaved benchmarks
+    elapsed_ns: u64,
+    executions: Vec<Execution>,
+    /// Number of executions whose markers were already recorded
+    recorded_executions: usize,
+    running_stats: RunningStats,
+    finished: bool,
+}
+
+impl<'a> BenchmarkRounds<'a> {
+    /// Prepare the rounds of a benchmark, checking the process settings that are permitted and
+    /// the output of the command, and calibrating the process spawn overhead if requested
+    pub fn new(
+        bench_uri: String,
+        command: &'a BenchmarkCommand,
+        config: &'a ExecutionOptions,
+    ) -> Result<Self> {
+        let timeout = command.parse_timeout()?;
+
+        let process_settings = resolve_permitted_settings(command, &config.process_settings)?;
+        if process_settings != ProcessSettings::default() {
+            debug!("Process settings: {process_settings:?}");
         }
-    } else {
-        extract_rounds_from_config(config)
-    };
 
-    let (min_time_ns, max_time_ns) = extract_time_constraints(config);
+        if command.assertions.checks_stdout() {
+            check_output(command, &process_settings, timeout)?;
+        }
 
-    // Validate that we have at least one constraint when warmup is disabled
-    if warmup_time_ns == 0
-        && rounds_to_perform.is_none()
-        && min_time_ns.is_none()
-        && max_time_ns.is_none()
-    {
-        bail!(
-            "When warmup is disabled, at least one constraint (min_rounds, max_rounds, min_time, or max_time) must be specified"
-        );
+        let spawn_overhead = config
+            .spawn_overhead
+            .map(|mode| -> Result<SpawnOverhead> {
+                let median_ns = calibrate_spawn_overhead(command, &process_settings, timeout)?;
+                info!(
+                    "Calibrated process spawn overhead: {}",
+                    format_ns(median_ns)
+                );
+                Ok(SpawnOverhead {
+                    median_ns: median_ns as f64,
+                    subtracted: mode == SpawnOverheadMode::Subtract,
+                })
+            })
+            .transpose()?;
+
+        let (min_time_ns, max_time_ns) = extract_time_constraints(config);
+        Ok(Self {
+            bench_uri,
+            command,
+            config,
+            timeout,
+            hooks: InstrumentHooks::instance(INTEGRATION_NAME, INTEGRATION_VERSION),
+            spawn_overhead,
+            process_settings,
+            rounds_to_perform: None,
+            iters_per_round: 1,
+            warmup_iters: 0,
+            min_precision_rounds: explicit_rounds(&config.min)
+                .unwrap_or(0)
+                .max(MIN_PRECISION_ROUNDS),
+            min_time_ns,
+            max_time_ns,
+            current_round: 0,
+            elapsed_ns: 0,
+            executions: Vec::new(),
+            recorded_executions: 0,
+            running_stats: RunningStats::default(),
+            finished: false,
+        })
     }
 
-    if let Some(rounds) = rounds_to_perform {
-        info!("Warmup done, now performing {rounds} rounds");
-    } else {
-        debug!(
-            "Running in degraded mode (no warmup, time-based constraints only): min_time={}, max_time={}",
-            min_time_ns
-                .map(format_ns)
-                .unwrap_or_else(|| "none".to_string()),
-            max_time_ns
-                .map(format_ns)
-                .unwrap_or_else(|| "none".to_string())
-        );
+    pub fn uri(&self) -> &str {
+        &self.bench_uri
     }
 
-    let mut times_per_round_ns = rounds_to_perform
-        .map(|r| Vec::with_capacity(r as usize))
-        .unwrap_or_default();
-    let mut current_round: u64 = 0;
+    /// Whether the stop conditions of the benchmark are met
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
 
-    debug!(
-        "Starting loop with ending conditions: \
-        rounds {rounds_to_perform:?}, \
-        min_time_ns {min_time_ns:?}, \
-        max_time_ns {max_time_ns:?}"
-    );
+    /// Run the warmup executions and compute the number of rounds to perform, along with the
+    /// number of executions per round. The benchmark must be started.
+    pub fn warmup(&mut self) -> Result<()> {
+        let config = self.config;
+        if config.warmup_time_ns > 0 {
+            match compute_rounds_from_warmup(config, || self.do_one_warmup_execution())? {
+                WarmupResult::EarlyReturn(execution) => {
+                    // Keep the single warmup execution as the only round, so the run still gets
+                    // profiling data
+                    self.executions.push(execution);
+                    self.current_round = 1;
+                    self.finished = true;
+                    return Ok(());
+                }
+                WarmupResult::Rounds {
+                    rounds,
+                    iters_per_round,
+                    warmup_iters,
+                } => {
+                    self.rounds_to_perform = Some(rounds);
+                    self.iters_per_round = iters_per_round;
+                    self.warmup_iters = warmup_iters;
+                }
+            }
+        } else {
+            self.rounds_to_perform = extract_rounds_from_config(config);
+            self.iters_per_round = match config.iters_per_round {
+                ItersPerRound::Count(count) => count,
+                ItersPerRound::Auto => 1,
+            };
+        }
 
-    let round_start_ts_ns = InstrumentHooks::current_timestamp();
+        // With a target precision, only an explicit max_rounds bounds the rounds, which stop as
+        // soon as the precision is reached
+        if config.target_precision.is_some() {
+            self.rounds_to_perform = explicit_rounds(&config.max);
+        }
 
-    let mut round_timestamps: Vec<(u64, u64)> = if let Some(rounds) = rounds_to_perform {
-        Vec::with_capacity(rounds as usize)
-    } else {
-        Vec::new()
-    };
+        // Validate that we have at least one constraint when warmup is disabled
+        if config.warmup_time_ns == 0
+            && self.rounds_to_perform.is_none()
+            && self.min_time_ns.is_none()
+            && self.max_time_ns.is_none()
+        {
+            bail!(
+                "When warmup is disabled, at least one constraint (min_rounds, max_rounds, min_time, or max_time) must be specified"
+            );
+        }
 
-    loop {
-        let current_round_timestamps = do_one_round()?;
-        // Only store timestamps for later processing in order to avoid overhead during the loop
-        round_timestamps.push(current_round_timestamps);
-        current_round += 1;
+        let iters_per_round = self.iters_per_round;
+        if let Some(target_precision) = config.target_precision {
+            info!(
+                "Warmup done, now performing rounds until the mean is within ±{:.2}%",
+                target_precision * 100.0
+            );
+        } else if let Some(rounds) = self.rounds_to_perform {
+            if iters_per_round > 1 {
+                info!(
+                    "Warmup done, now performing {rounds} rounds of {iters_per_round} iterations"
+                );
+            } else {
+                info!("Warmup done, now performing {rounds} rounds");
+            }
+        } else {
+            debug!(
+                "Running in degraded mode (no warmup, time-based constraints only): min_time={}, max_time={}",
+                self.min_time_ns
+                    .map(format_ns)
+                    .unwrap_or_else(|| "none".to_string()),
+                self.max_time_ns
+                    .map(format_ns)
+                    .unwrap_or_else(|| "none".to_string())
+            );
+        }
 
-        let elapsed_ns = InstrumentHooks::current_timestamp() - round_start_ts_ns;
+        debug!(
+            "Starting loop with ending conditions: \
+            rounds {:?}, \
+            min_time_ns {:?}, \
+            max_time_ns {:?}",
+            self.rounds_to_perform, self.min_time_ns, self.max_time_ns
+        );
+
+        if let Some(rounds) = self.rounds_to_perform {
+            self.executions
+                .reserve((rounds * self.iters_per_round) as usize);
+        }
+
+        Ok(())
+    }
+
+    /// Run one round of executions and check the stop conditions. The benchmark must be started.
+    pub fn run_round(&mut self) -> Result<()> {
+        let round_start_ts_ns = InstrumentHooks::current_timestamp();
+        self.run_round_hook(HookKind::BeforeEach)?;
+        for _ in 0..self.iters_per_round {
+            let execution = self.do_one_execution()?;
+            // Only store executions for later processing in order to avoid overhead during the loop
+            self.executions.push(execution);
+        }
+        self.run_round_hook(HookKind::AfterEach)?;
+        self.elapsed_ns += InstrumentHooks::current_timestamp() - round_start_ts_ns;
+        self.current_round += 1;
+
+        let round = &self.executions[self.executions.len() - self.iters_per_round as usize..];
+
+        let current_round = self.current_round;
+        let elapsed_ns = self.elapsed_ns;
+        let reached_target_precision = if let Some(target_precision) = self.config.target_precision
+        {
+            let round_time_ns = round
+                .iter()
+                .map(|execution| self.execution_time_ns(execution))
+                .sum::<u128>();
+            self.running_stats
+                .push(round_time_ns as f64 / self.iters_per_round as f64);
+
+            current_round >= self.min_precision_rounds
+                && self
+                    .running_stats
+                    .relative_precision()
+                    .is_some_and(|precision| precision <= target_precision)
+        } else {
+            false
+        };
 
         // Check stop conditions
-        let reached_max_rounds = rounds_to_perform.is_some_and(|r| current_round >= r);
-        let reached_max_time = max_time_ns.is_some_and(|t| elapsed_ns >= t);
-        let reached_min_time = min_time_ns.is_some_and(|t| elapsed_ns >= t);
+        let reached_max_rounds = self.rounds_to_perform.is_some_and(|r| current_round >= r);
+        let reached_max_time = self.max_time_ns.is_some_and(|t| elapsed_ns >= t);
+        let reached_min_time = self.min_time_ns.is_some_and(|t| elapsed_ns >= t);
 
         // Stop if we hit max_time
         if reached_max_time {
             debug!(
                 "Reached maximum time limit after {current_round} rounds (elapsed: {}, max: {})",
                 format_ns(elapsed_ns),
-                format_ns(max_time_ns.unwrap())
+                format_ns(self.max_time_ns.unwrap())
             );
-            break;
+            self.finished = true;
         }
-
         // Stop if we hit max_rounds
-        if reached_max_rounds {
-            break;
+        else if reached_max_rounds {
+            self.finished = true;
+        }
+        // Stop once the target precision is reached, after min_time if any
+        else if reached_target_precision && (self.min_time_ns.is_none() || reached_min_time) {
+            debug!(
+                "Reached target precision after {current_round} rounds (elapsed: {})",
+                format_ns(elapsed_ns)
+            );
+            self.finished = true;
         }
-
         // If no rounds constraint, stop when min_time is reached
-        if rounds_to_perform.is_none() && reached_min_time {
+        else if self.rounds_to_perform.is_none()
+            && self.config.target_precision.is_none()
+            && reached_min_time
+        {
             debug!(
                 "Reached minimum time after {current_round} rounds (elapsed: {}, min: {})",
                 format_ns(elapsed_ns),
-                format_ns(min_time_ns.unwrap())
+                format_ns(self.min_time_ns.unwrap())
             );
-            break;
+            self.finished = true;
         }
+
+        Ok(())
     }
 
-    // Record timestamps
-    for (start, end) in round_timestamps {
-        hooks.add_benchmark_timestamps(start, end);
-        times_per_round_ns.push((end - start) as u128);
+    /// Add the benchmark markers of the executions that ran since the last call
+    pub fn record_markers(&mut self) {
+        for execution in &self.executions[self.recorded_executions..] {
+            self.hooks
+                .add_benchmark_timestamps(execution.start_ts_ns, execution.end_ts_ns);
+        }
+        self.recorded_executions = self.executions.len();
     }
 
-    hooks.stop_benchmark().unwrap();
-    hooks.set_executed_benchmark(&bench_uri).unwrap();
+    /// Compute the measurements of the rounds that ran
+    pub fn finish(self) -> MeasuredRounds {
+        if let Some(target_precision) = self.config.target_precision {
+            let precision = self.running_stats.relative_precision();
+            if precision.is_none_or(|precision| precision > target_precision) {
+                warn!(
+                    "Target precision of ±{:.2}% not reached after {} rounds (achieved: {})",
+                    target_precision * 100.0,
+                    self.current_round,
+                    precision
+                        .map(|precision| format!("±{:.2}%", precision * 100.0))
+                        .unwrap_or_else(|| "unknown".to_string())
+                );
+            }
+        }
+
+        // The time of a round is the sum of its executions, excluding the hooks
+        let times_per_round_ns = self
+            .executions
+            .chunks(self.iters_per_round as usize)
+            .map(|round| {
+                round
+                    .iter()
+                    .map(|execution| self.execution_time_ns(execution))
+                    .sum()
+            })
+            .collect();
+        let usages = self
+            .executions
+            .iter()
+            .map(|execution| execution.usage.clone())
+            .collect::<Vec<_>>();
+
+        MeasuredRounds {
+            times_per_round_ns,
+            iters_per_round: self.iters_per_round,
+            warmup_iters: self.warmup_iters,
+            spawn_overhead: self.spawn_overhead,
+            resource_usage: ResourceUsage::aggregate(&usages),
+            process_settings: (self.process_settings != ProcessSettings::default())
+                .then_some(self.process_settings),
+        }
+    }
 
-    Ok(times_per_round_ns)
+    /// Per-round hooks run with the benchmark stopped, to keep them out of the profile
+    fn run_round_hook(&self, kind: HookKind) -> Result<()> {
+        if self.command.hooks.get(kind).is_none() {
+            return Ok(());
+        }
+        self.hooks.stop_benchmark().unwrap();
+        let result = self.command.run_hook(kind);
+        self.hooks.start_benchmark().unwrap();
+        result
+    }
+
+    /// Warmup executions are rounds of a single execution, run between the per-round hooks
+    fn do_one_warmup_execution(&self) -> Result<Execution> {
+        self.run_round_hook(HookKind::BeforeEach)?;
+        let execution = self.do_one_execution()?;
+        self.run_round_hook(HookKind::AfterEach)?;
+        Ok(execution)
+    }
+
+    fn do_one_execution(&self) -> Result<Execution> {
+        let mut cmd = self.command.to_process_command()?;
+        apply_process_settings(&mut cmd, &self.process_settings);
+        let child = BenchmarkProcess::spawn(&mut cmd, self.timeout)?;
+        let bench_round_start_ts_ns = InstrumentHooks::current_timestamp();
+        let (status, usage) = child.wait_with_usage()?;
+
+        let bench_round_end_ts_ns = InstrumentHooks::current_timestamp();
+
+        self.command.check_execution(status, None)?;
+
+        Ok(Execution {
+            start_ts_ns: bench_round_start_ts_ns,
+            end_ts_ns: bench_round_end_ts_ns,
+            usage,
+        })
+    }
+
+    /// Time of an execution, without the spawn overhead when it is subtracted
+    fn execution_time_ns(&self, execution: &Execution) -> u128 {
+        let overhead_ns = match &self.spawn_overhead {
+            Some(overhead) if overhead.subtracted => overhead.median_ns as u64,
+            _ => 0,
+        };
+        (execution.end_ts_ns - execution.start_ts_ns).saturating_sub(overhead_ns) as u128
+    }
+}
+
+/// Execute the command once with its standard output captured and checked, before the rounds
+/// so that reading the output is not measured
+fn check_output(
+    command: &BenchmarkCommand,
+    process_settings: &ProcessSettings,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    command.run_hook(HookKind::BeforeEach)?;
+    let mut cmd = command.to_checked_process_command()?;
+    apply_process_settings(&mut cmd, process_settings);
+    let mut child = BenchmarkProcess::spawn(&mut cmd, timeout)?;
+    let stdout = child.read_stdout()?;
+    let status = child.wait()?;
+    command.check_execution(status, stdout)?;
+    command.run_hook(HookKind::AfterEach)
+}
+
+/// Time executions of a no-op executable spawned the same way as the command, returning their
+/// median time
+fn calibrate_spawn_overhead(
+    command: &BenchmarkCommand,
+    process_settings: &ProcessSettings,
+    timeout: Option<Duration>,
+) -> Result<u64> {
+    let mut times_ns = (0..SPAWN_OVERHEAD_CALIBRATION_ITERS)
+        .map(|_| -> Result<u64> {
+            let mut cmd = command.to_noop_process_command();
+            apply_process_settings(&mut cmd, process_settings);
+            let child = BenchmarkProcess::spawn(&mut cmd, timeout)
+                .context("Failed to calibrate the process spawn overhead")?;
+            let start = InstrumentHooks::current_timestamp();
+            let status = child.wait()?;
+            let end = InstrumentHooks::current_timestamp();
+
+            if !status.success() {
+                bail!("Spawn overhead calibration command exited with non-zero status: {status}");
+            }
+            Ok(end - start)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    times_ns.sort_unstable();
+    Ok(times_ns[times_ns.len() / 2])
 }
 
 enum WarmupResult {
-    /// Warmup exceeded max_time constraint with a single run, return early with this single timestamp pair
-    EarlyReturn { start: u64, end: u64 },
-    /// Continue with this many rounds
-    Rounds(u64),
+    /// Warmup exceeded max_time constraint with a single run, return early with this single execution
+    EarlyReturn(Execution),
+    /// Continue with this many rounds of `iters_per_round` executions
+    Rounds {
+        rounds: u64,
+        iters_per_round: u64,
+        warmup_iters: u64,
+    },
 }
 
-/// Run warmup rounds and compute the number of benchmark rounds to perform
-fn compute_rounds_from_warmup<F>(config: &ExecutionOptions, do_one_round: F) -> Result<WarmupResult>
+/// Run warmup executions and compute the number of benchmark rounds to perform, along with the
+/// number of executions per round
+fn compute_rounds_from_warmup<F>(
+    config: &ExecutionOptions,
+    do_one_execution: F,
+) -> Result<WarmupResult>
 where
-    F: Fn() -> Result<(u64, u64)>,
+    F: Fn() -> Result<Execution>,
 {
-    let mut warmup_timestamps: Vec<(u64, u64)> = Vec::new();
+    let mut warmup_executions: Vec<Execution> = Vec::new();
     let warmup_start_ts_ns = InstrumentHooks::current_timestamp();
 
     while InstrumentHooks::current_timestamp() < warmup_start_ts_ns + config.warmup_time_ns {
-        let (start, end) = do_one_round()?;
-        warmup_timestamps.push((start, end));
+        warmup_executions.push(do_one_execution()?);
     }
-    let warmup_end_ts_ns = InstrumentHooks::current_timestamp();
+    let warmup_iters = warmup_executions.len() as u64;
 
     // Check if single warmup round already exceeded max_time
-    if let [(start, end)] = warmup_timestamps.as_slice() {
-        let single_warmup_round_duration_ns = end - start;
+    if let [execution] = warmup_executions.as_slice() {
+        let single_warmup_round_duration_ns = execution.end_ts_ns - execution.start_ts_ns;
         match config.max {
             Some(RoundOrTime::TimeNs(time_ns)) | Some(RoundOrTime::Both { time_ns, .. }) => {
                 if time_ns <= single_warmup_round_duration_ns {
@@ -180,20 +597,27 @@ where
                         format_ns(single_warmup_round_duration_ns),
                         format_ns(time_ns)
                     );
-                    return Ok(WarmupResult::EarlyReturn {
-                        start: *start,
-                        end: *end,
-                    });
+                    let execution = warmup_executions.pop().unwrap();
+                    return Ok(WarmupResult::EarlyReturn(execution));
                 }
             }
             _ => { /* No max time constraint */ }
         }
     }
 
-    info!("Completed {} warmup rounds", warmup_timestamps.len());
-
-    let average_time_per_round_ns =
-        (warmup_end_ts_ns - warmup_start_ts_ns) / warmup_timestamps.len() as u64;
+    info!("Completed {warmup_iters} warmup rounds");
+
+    // Averaged over the executions only, the hooks around them are not part of the rounds
+    let warmup_executions_time_ns = warmup_executions
+        .iter()
+        .map(|execution| execution.end_ts_ns - execution.start_ts_ns)
+        .sum::<u64>();
+    let average_time_per_iter_ns = (warmup_executions_time_ns / warmup_iters).max(1);
+    let iters_per_round = match config.iters_per_round {
+        ItersPerRound::Count(count) => count,
+        ItersPerRound::Auto => MIN_ROUND_TIME_NS.div_ceil(average_time_per_iter_ns),
+    };
+    let average_time_per_round_ns = average_time_per_iter_ns * iters_per_round;
 
     let actual_min_rounds = compute_min_rounds(config, average_time_per_round_ns);
     let actual_max_rounds = compute_max_rounds(config, average_time_per_round_ns);
@@ -213,7 +637,11 @@ where
         }
     };
 
-    Ok(WarmupResult::Rounds(rounds))
+    Ok(WarmupResult::Rounds {
+        rounds,
+        iters_per_round,
+        warmup_iters,
+    })
 }
 
 /// Compute the minimum number of rounds based on config and average round time
@@ -258,6 +686,14 @@ fn extract_rounds_from_config(config: &ExecutionOptions) -> Option<u64> {
     }
 }
 
+/// Extract the number of rounds explicitly set in a min or max bound
+fn explicit_rounds(bound: &Option<RoundOrTime>) -> Option<u64> {
+    match bound {
+        Some(RoundOrTime::Rounds(rounds)) | Some(RoundOrTime::Both { rounds, .. }) => Some(*rounds),
+        _ => None,
+    }
+}
+
 /// Extract time constraints from config for stop conditions
 fn extract_time_constraints(config: &ExecutionOptions) -> (Option<u64>, Option<u64>) {
     let min_time_ns = match &config.min {
@@ -1,4 +1,7 @@
+use super::process_settings::ProcessSettingsArgs;
 use crate::prelude::*;
+use clap::ValueEnum;
+use runner_shared::walltime_results::ProcessSettings;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -9,7 +12,7 @@ const DEFAULT_MAX_TIME_NS: u64 = 3_000_000_000; // 3 seconds
 /// Parse a duration string into nanoseconds
 /// Supports humantime format: "1s", "500ms", "1.5s", "2m", "1h", etc.
 /// Also supports pure numbers interpreted as seconds (e.g., "2" = 2s, "1.5" = 1.5s)
-fn parse_duration_to_ns(s: &str) -> Result<u64> {
+pub(crate) fn parse_duration_to_ns(s: &str) -> Result<u64> {
     let s = s.trim();
 
     // Try parsing as pure number first (interpret as seconds)
@@ -24,6 +27,20 @@ fn parse_duration_to_ns(s: &str) -> Result<u64> {
     Ok(duration.as_nanos() as u64)
 }
 
+/// Parse a percentage string into a fraction, e.g. "1%" or "1" into 0.01
+fn parse_percentage(s: &str) -> Result<f64> {
+    let s = s.trim();
+    let percent: f64 = s
+        .strip_suffix('%')
+        .unwrap_or(s)
+        .trim()
+        .parse()
+        .with_context(|| {
+            format!("Invalid percentage format: '{s}'. Expected format like '1%' or '0.5%'")
+        })?;
+    Ok(percent / 100.0)
+}
+
 /// Arguments for walltime execution configuration
 ///
 /// ⚠️ Make sure to update WalltimeExecutionArgs::to_cli_args() when fields change, else the runner
@@ -75,6 +92,115 @@ pub struct WalltimeExecutionArgs {
     /// Default: undefined (determined by timing constraints)
     #[arg(long, value_name = "COUNT")]
     pub min_rounds: Option<u64>,
+
+    /// Number of times the command is executed in each round, or `auto` to choose it during
+    /// warmup so that a round lasts at least 1ms (1 when warmup is disabled).
+    /// Batching very short commands averages out the process spawn noise, the time of a round is
+    /// divided by this count in the results.
+    ///
+    /// Format: positive integer or `auto`
+    /// Default: 1
+    #[arg(long, value_name = "COUNT")]
+    pub iters_per_round: Option<String>,
+
+    /// Calibrate the process spawn overhead before the rounds, by timing a no-op executable
+    /// spawned the same way as the command.
+    /// `measure` records the overhead in the results, `subtract` also removes it from the time
+    /// of each execution.
+    ///
+    /// Format: `measure` or `subtract`
+    /// Default: undefined (no calibration)
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub spawn_overhead: Option<SpawnOverheadMode>,
+
+    /// Relative precision of the mean to reach before stopping: rounds keep running until the
+    /// 95% confidence interval of the mean is narrower than ±this percentage of it.
+    /// Noisy benchmarks get more rounds, stable ones finish sooner. Still capped by max_time
+    /// and max_rounds, or by the default max_time when neither is set, and min_time and
+    /// min_rounds are still honored.
+    ///
+    /// Format: percentage (e.g., "1%", "0.5%")
+    /// Default: undefined (the rounds are computed from the warmup)
+    #[arg(long, value_name = "PERCENT")]
+    pub target_precision: Option<String>,
+
+    #[command(flatten)]
+    #[serde(flatten)]
+    pub process_settings: ProcessSettingsArgs,
+}
+
+/// Arguments scheduling the rounds of several benchmarks, which apply to all the benchmarks even
+/// when they are read from stdin
+///
+/// ⚠️ Make sure to update SchedulingArgs::to_cli_args() when fields change, else the runner
+/// will not properly forward arguments
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct SchedulingArgs {
+    /// Interleave the rounds of the benchmarks instead of running each benchmark to completion,
+    /// so that drifts of the machine performance over time (e.g., thermal throttling) don't bias
+    /// the last benchmarks. Every benchmark is warmed up first, then one round of each is run in
+    /// turn until they all meet their stop conditions.
+    #[arg(long)]
+    pub interleave: bool,
+
+    /// Shuffle the order of the benchmarks in each turn of interleaved rounds, implies
+    /// --interleave. The seed is logged and recorded in the results to reproduce the order.
+    #[arg(long)]
+    pub shuffle: bool,
+
+    /// Seed of the shuffled order of the rounds, implies --shuffle.
+    ///
+    /// Default: random
+    #[arg(long, value_name = "SEED")]
+    pub shuffle_seed: Option<u64>,
+}
+
+impl SchedulingArgs {
+    /// Convert SchedulingArgs back to CLI argument strings
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if self.interleave {
+            args.push("--interleave".to_string());
+        }
+
+        if self.shuffle {
+            args.push("--shuffle".to_string());
+        }
+
+        if let Some(shuffle_seed) = &self.shuffle_seed {
+            args.push("--shuffle-seed".to_string());
+            args.push(shuffle_seed.to_string());
+        }
+
+        args
+    }
+
+    pub fn is_interleaved(&self) -> bool {
+        self.interleave || self.is_shuffled()
+    }
+
+    pub fn is_shuffled(&self) -> bool {
+        self.shuffle || self.shuffle_seed.is_some()
+    }
+
+    /// Seed of the shuffled order of the rounds, drawn at random if shuffling without a seed
+    pub fn resolve_shuffle_seed(&self) -> Option<u64> {
+        if !self.is_shuffled() {
+            return None;
+        }
+        Some(self.shuffle_seed.unwrap_or_else(rand::random))
+    }
+}
+
+/// How the process spawn overhead is handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpawnOverheadMode {
+    /// Record the calibrated overhead in the results
+    Measure,
+    /// Record the calibrated overhead and subtract it from the time of each execution
+    Subtract,
 }
 
 impl WalltimeExecutionArgs {
@@ -110,6 +236,29 @@ impl WalltimeExecutionArgs {
             args.push(min_rounds.to_string());
         }
 
+        if let Some(iters_per_round) = &self.iters_per_round {
+            args.push("--iters-per-round".to_string());
+            args.push(iters_per_round.clone());
+        }
+
+        if let Some(spawn_overhead) = &self.spawn_overhead {
+            args.push("--spawn-overhead".to_string());
+            args.push(
+                spawn_overhead
+                    .to_possible_value()
+                    .expect("no skipped variant")
+                    .get_name()
+                    .to_string(),
+            );
+        }
+
+        if let Some(target_precision) = &self.target_precision {
+            args.push("--target-precision".to_string());
+            args.push(target_precision.clone());
+        }
+
+        args.extend(self.process_settings.to_cli_args());
+
         args
     }
 }
@@ -125,11 +274,41 @@ pub(crate) enum RoundOrTime {
     Both { rounds: u64, time_ns: u64 },
 }
 
+/// Number of executions of the command per round
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ItersPerRound {
+    Count(u64),
+    /// Chosen during warmup so that a round lasts at least a minimum time
+    Auto,
+}
+
+/// Parse the executions per round, a positive integer or `auto`
+fn parse_iters_per_round(s: &str) -> Result<ItersPerRound> {
+    let s = s.trim();
+    if s == "auto" {
+        return Ok(ItersPerRound::Auto);
+    }
+
+    let count: u64 = s.parse().with_context(|| {
+        format!("Invalid iters_per_round: '{s}'. Expected a positive integer or 'auto'")
+    })?;
+    if count == 0 {
+        bail!("iters_per_round must be greater than 0");
+    }
+    Ok(ItersPerRound::Count(count))
+}
+
 #[derive(Debug)]
 pub struct ExecutionOptions {
     pub(crate) warmup_time_ns: u64,
     pub(crate) min: Option<RoundOrTime>,
     pub(crate) max: Option<RoundOrTime>,
+    pub(crate) iters_per_round: ItersPerRound,
+    pub(crate) spawn_overhead: Option<SpawnOverheadMode>,
+    /// Relative half-width of the confidence interval of the mean at which the rounds stop
+    pub(crate) target_precision: Option<f64>,
+    /// Scheduling and address space settings of the executions
+    pub(crate) process_settings: ProcessSettings,
 }
 
 impl TryFrom<WalltimeExecutionArgs> for ExecutionOptions {
@@ -156,6 +335,13 @@ impl TryFrom<WalltimeExecutionArgs> for ExecutionOptions {
             .transpose()
             .context("Invalid warmup_time")?;
 
+        let min_time_ns = args
+            .min_time
+            .as_ref()
+            .map(|s| parse_duration_to_ns(s))
+            .transpose()
+            .context("Invalid min_time")?;
+
         let max_time_ns = args
             .max_time
             .as_ref()
@@ -164,21 +350,19 @@ impl TryFrom<WalltimeExecutionArgs> for ExecutionOptions {
             .context("Invalid max_time")?
             .unwrap_or_else(|| {
                 // No max_time provided, use default only if no round-based constraints are set
-                if args.max_rounds.is_some() || args.min_rounds.is_some() || args.min_time.is_some()
-                {
+                if args.max_rounds.is_some() {
+                    0
+                } else if args.target_precision.is_some() {
+                    // The target precision may never be reached, e.g. by a noisy command, so the
+                    // rounds are always capped, after min_time if any
+                    DEFAULT_MAX_TIME_NS.max(min_time_ns.unwrap_or(0))
+                } else if args.min_rounds.is_some() || args.min_time.is_some() {
                     0
                 } else {
                     DEFAULT_MAX_TIME_NS
                 }
             });
 
-        let min_time_ns = args
-            .min_time
-            .as_ref()
-            .map(|s| parse_duration_to_ns(s))
-            .transpose()
-            .context("Invalid min_time")?;
-
         // Validation: min_xxx cannot be greater than max_xxx (for same dimension)
         if max_time_ns > 0 {
             if let Some(min) = min_time_ns {
@@ -198,6 +382,27 @@ impl TryFrom<WalltimeExecutionArgs> for ExecutionOptions {
             }
         }
 
+        let iters_per_round = args
+            .iters_per_round
+            .as_deref()
+            .map(parse_iters_per_round)
+            .transpose()?
+            .unwrap_or(ItersPerRound::Count(1));
+
+        let target_precision = args
+            .target_precision
+            .as_ref()
+            .map(|s| parse_percentage(s))
+            .transpose()
+            .context("Invalid target_precision")?;
+        if let Some(precision) = target_precision {
+            if precision <= 0.0 || precision >= 1.0 {
+                bail!("target_precision must be between 0% and 100%, exclusive");
+            }
+        }
+
+        let process_settings = args.process_settings.to_settings()?;
+
         // Build min/max using RoundOrTime enum
         // Now we allow mixing time and rounds constraints across min/max bounds
         let min = match (args.min_rounds, min_time_ns) {
@@ -218,6 +423,10 @@ impl TryFrom<WalltimeExecutionArgs> for ExecutionOptions {
             warmup_time_ns: warmup_time_ns.unwrap_or(DEFAULT_WARMUP_TIME_NS),
             min,
             max,
+            iters_per_round,
+            spawn_overhead: args.spawn_overhead,
+            target_precision,
+            process_settings,
         })
     }
 }
@@ -228,6 +437,10 @@ impl Default for ExecutionOptions {
             warmup_time_ns: DEFAULT_WARMUP_TIME_NS,
             min: None,
             max: Some(RoundOrTime::TimeNs(DEFAULT_MAX_TIME_NS)),
+            iters_per_round: ItersPerRound::Count(1),
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: ProcessSettings::default(),
         }
     }
 }
@@ -283,6 +496,10 @@ mod tests {
             min_time: None,
             max_rounds: Some(10),
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into()
         .unwrap();
@@ -317,6 +534,10 @@ mod tests {
             min_time: None,
             max_rounds: None,
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
 
@@ -341,6 +562,10 @@ mod tests {
             min_time: Some("2s".to_string()),
             max_rounds: Some(10),
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
 
@@ -360,6 +585,10 @@ mod tests {
             min_time: None,
             max_rounds: None,
             min_rounds: Some(5),
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
 
@@ -377,6 +606,10 @@ mod tests {
             min_time: Some("10s".to_string()), // min > max!
             max_rounds: None,
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
 
@@ -396,6 +629,10 @@ mod tests {
             min_time: None,
             max_rounds: Some(10),
             min_rounds: Some(50), // min > max!
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
 
@@ -407,6 +644,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validation_zero_iters_per_round() {
+        let result: Result<ExecutionOptions> = WalltimeExecutionArgs {
+            warmup_time: None,
+            max_time: None,
+            min_time: None,
+            max_rounds: None,
+            min_rounds: None,
+            iters_per_round: Some("0".to_string()),
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
+        }
+        .try_into();
+
+        let err = result.unwrap_err().to_string();
+        assert_eq!(err, "iters_per_round must be greater than 0");
+    }
+
+    #[test]
+    fn test_iters_per_round() {
+        let args = |iters_per_round: Option<&str>| WalltimeExecutionArgs {
+            iters_per_round: iters_per_round.map(str::to_string),
+            ..Default::default()
+        };
+
+        let opts = ExecutionOptions::try_from(args(None)).unwrap();
+        assert_eq!(opts.iters_per_round, ItersPerRound::Count(1));
+        let opts = ExecutionOptions::try_from(args(Some("4"))).unwrap();
+        assert_eq!(opts.iters_per_round, ItersPerRound::Count(4));
+        let opts = ExecutionOptions::try_from(args(Some("auto"))).unwrap();
+        assert_eq!(opts.iters_per_round, ItersPerRound::Auto);
+
+        let err = ExecutionOptions::try_from(args(Some("many"))).unwrap_err();
+        assert!(err.to_string().contains("Invalid iters_per_round"), "{err}");
+    }
+
+    #[test]
+    fn test_target_precision() {
+        let args = |target_precision: &str| WalltimeExecutionArgs {
+            target_precision: Some(target_precision.to_string()),
+            ..Default::default()
+        };
+
+        let opts = ExecutionOptions::try_from(args("1%")).unwrap();
+        assert_eq!(opts.target_precision, Some(0.01));
+        let opts = ExecutionOptions::try_from(args(" 0.5 ")).unwrap();
+        assert_eq!(opts.target_precision, Some(0.005));
+
+        let err = ExecutionOptions::try_from(args("high")).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid target_precision"),
+            "{err}"
+        );
+        let err = ExecutionOptions::try_from(args("0%")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "target_precision must be between 0% and 100%, exclusive"
+        );
+    }
+
+    #[test]
+    fn test_target_precision_is_capped_by_default_max_time() {
+        let opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+            target_precision: Some("1%".to_string()),
+            min_rounds: Some(10),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(opts.min, Some(RoundOrTime::Rounds(10)));
+        assert_eq!(opts.max, Some(RoundOrTime::TimeNs(DEFAULT_MAX_TIME_NS)));
+
+        let opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+            target_precision: Some("1%".to_string()),
+            min_time: Some("5s".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(opts.max, Some(RoundOrTime::TimeNs(5_000_000_000)));
+
+        // An explicit max_rounds bounds the rounds on its own
+        let opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+            target_precision: Some("1%".to_string()),
+            min_rounds: Some(10),
+            max_rounds: Some(100),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(opts.max, Some(RoundOrTime::Rounds(100)));
+    }
+
     #[test]
     fn test_no_warmup_with_time_only_is_allowed() {
         // No warmup + time constraints only is now allowed (degraded mode)
@@ -417,6 +745,10 @@ mod tests {
             min_time: None,
             max_rounds: None, // No rounds specified
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
 
@@ -435,6 +767,10 @@ mod tests {
             min_time: None,
             max_rounds: Some(5),
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
         assert!(result.is_ok());
@@ -446,6 +782,10 @@ mod tests {
             min_time: Some("2s".to_string()),
             max_rounds: None,
             min_rounds: Some(100),
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
         assert!(result.is_ok());
@@ -457,6 +797,10 @@ mod tests {
             min_time: Some("2s".to_string()),
             max_rounds: None,
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
         assert!(result.is_ok());
@@ -468,6 +812,10 @@ mod tests {
             min_time: None,
             max_rounds: Some(100),
             min_rounds: Some(10),
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
         assert!(result.is_ok());
@@ -479,6 +827,10 @@ mod tests {
             min_time: None,
             max_rounds: Some(50),
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         }
         .try_into();
         assert!(result.is_ok());
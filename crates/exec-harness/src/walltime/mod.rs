@@ -1,51 +1,81 @@
 mod benchmark_loop;
 mod config;
+mod process_settings;
 
+use benchmark_loop::{BenchmarkRounds, MeasuredRounds};
 pub use config::ExecutionOptions;
+pub use config::SchedulingArgs;
+pub use config::SpawnOverheadMode;
 pub use config::WalltimeExecutionArgs;
+pub(crate) use config::parse_duration_to_ns;
+pub use process_settings::ProcessSettingsArgs;
+pub use process_settings::parse_cpu_list;
 use runner_shared::walltime_results::Creator;
+use runner_shared::walltime_results::Interleaving;
+use runner_shared::walltime_results::Precision;
 use runner_shared::walltime_results::WalltimeBenchmark;
 pub use runner_shared::walltime_results::WalltimeResults;
 
 use crate::BenchmarkCommand;
 use crate::constants::INTEGRATION_NAME;
 use crate::constants::INTEGRATION_VERSION;
+use crate::hooks::with_setups_and_teardowns;
 use crate::prelude::*;
 use crate::uri::NameAndUri;
 use crate::uri::generate_name_and_uri;
 
-pub fn perform(commands: Vec<BenchmarkCommand>) -> Result<()> {
-    let mut walltime_benchmarks = Vec::with_capacity(commands.len());
-
-    for cmd in commands {
-        let name_and_uri = generate_name_and_uri(&cmd.name, &cmd.command);
-        name_and_uri.print_executing();
-        let execution_options: ExecutionOptions = cmd.walltime_args.try_into()?;
-
-        let NameAndUri {
-            name: bench_name,
-            uri: bench_uri,
-            ..
-        } = name_and_uri;
-
-        let times_per_round_ns =
-            benchmark_loop::run_rounds(bench_uri.clone(), cmd.command, &execution_options)?;
+pub fn perform(commands: Vec<BenchmarkCommand>, scheduling: &SchedulingArgs) -> Result<()> {
+    let mut benchmarks = Vec::with_capacity(commands.len());
+    for cmd in &commands {
+        let name_and_uri = generate_name_and_uri(&cmd.name, &cmd.command, &cmd.params);
+        let execution_options: ExecutionOptions = cmd.walltime_args.clone().try_into()?;
+        benchmarks.push((name_and_uri, execution_options));
+    }
 
-        // Collect walltime results
-        let max_time_ns = times_per_round_ns.iter().copied().max();
+    let mut interleaving = None;
+    let measured_rounds = if scheduling.is_interleaved() {
+        let shuffle_seed = scheduling.resolve_shuffle_seed();
+        match shuffle_seed {
+            Some(seed) => info!(
+                "Interleaving the rounds of {} benchmarks, shuffled with seed {seed}",
+                benchmarks.len()
+            ),
+            None => info!("Interleaving the rounds of {} benchmarks", benchmarks.len()),
+        }
+        interleaving = Some(Interleaving { shuffle_seed });
 
-        let walltime_benchmark = WalltimeBenchmark::from_runtime_data(
-            bench_name.clone(),
-            bench_uri.clone(),
-            vec![1; times_per_round_ns.len()],
-            times_per_round_ns,
-            max_time_ns,
-        );
+        with_setups_and_teardowns(&commands, || {
+            let mut rounds = Vec::with_capacity(commands.len());
+            for (cmd, (name_and_uri, execution_options)) in commands.iter().zip(&benchmarks) {
+                name_and_uri.print_executing();
+                rounds.push(BenchmarkRounds::new(
+                    name_and_uri.uri.clone(),
+                    cmd,
+                    execution_options,
+                )?);
+            }
+            benchmark_loop::run_interleaved_rounds(rounds, shuffle_seed)
+        })?
+    } else {
+        let mut measured_rounds = Vec::with_capacity(commands.len());
+        for (cmd, (name_and_uri, execution_options)) in commands.iter().zip(&benchmarks) {
+            name_and_uri.print_executing();
+            measured_rounds.push(cmd.with_setup_and_teardown(|| {
+                benchmark_loop::run_rounds(name_and_uri.uri.clone(), cmd, execution_options)
+            })?);
+        }
+        measured_rounds
+    };
 
-        walltime_benchmarks.push(walltime_benchmark);
-    }
+    let walltime_benchmarks = benchmarks
+        .into_iter()
+        .zip(measured_rounds)
+        .map(|((name_and_uri, execution_options), measured)| {
+            to_walltime_benchmark(name_and_uri, &execution_options, measured)
+        })
+        .collect();
 
-    let walltime_results = WalltimeResults::new(
+    let mut walltime_results = WalltimeResults::new(
         Creator {
             name: INTEGRATION_NAME.to_string(),
             version: INTEGRATION_VERSION.to_string(),
@@ -54,17 +84,45 @@ pub fn perform(commands: Vec<BenchmarkCommand>) -> Result<()> {
         walltime_benchmarks,
     )
     .expect("Failed to create walltime results");
+    walltime_results.interleaving = interleaving;
 
     walltime_results
-        .save_to_file(
-            std::env::var("CODSPEED_PROFILE_FOLDER")
-                .map(std::path::PathBuf::from)
-                .unwrap_or_else(|_| std::env::current_dir().unwrap().join(".codspeed")),
-        )
+        .save_to_file(crate::profile_folder())
         .context("Failed to save walltime results")?;
 
     Ok(())
 }
 
+/// Collect the walltime results of the measured rounds of a benchmark
+fn to_walltime_benchmark(
+    name_and_uri: NameAndUri,
+    execution_options: &ExecutionOptions,
+    measured: MeasuredRounds,
+) -> WalltimeBenchmark {
+    let NameAndUri {
+        name: bench_name,
+        uri: bench_uri,
+        ..
+    } = name_and_uri;
+    let max_time_ns = measured.times_per_round_ns.iter().copied().max();
+
+    let mut walltime_benchmark = WalltimeBenchmark::from_runtime_data(
+        bench_name,
+        bench_uri,
+        vec![measured.iters_per_round as u128; measured.times_per_round_ns.len()],
+        measured.times_per_round_ns,
+        max_time_ns,
+    );
+    walltime_benchmark.stats.warmup_iters = measured.warmup_iters;
+    walltime_benchmark.spawn_overhead = measured.spawn_overhead;
+    walltime_benchmark.resource_usage = measured.resource_usage;
+    walltime_benchmark.process_settings = measured.process_settings;
+    walltime_benchmark.precision = execution_options.target_precision.map(|target| Precision {
+        target,
+        achieved: walltime_benchmark.stats.relative_precision(),
+    });
+    walltime_benchmark
+}
+
 #[cfg(test)]
 mod tests;
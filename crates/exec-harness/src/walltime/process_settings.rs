@@ -0,0 +1,334 @@
+use crate::BenchmarkCommand;
+use crate::prelude::*;
+use runner_shared::walltime_results::ProcessSettings;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+/// Range of the nice values, from the highest to the lowest priority
+const NICE_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
+/// Range of the real-time priorities of the SCHED_FIFO policy
+const SCHED_FIFO_PRIORITY_RANGE: std::ops::RangeInclusive<u32> = 1..=99;
+
+/// Scheduling and address space settings of the benchmarked process, to reduce the noise of the
+/// measures
+///
+/// ⚠️ Make sure to update ProcessSettingsArgs::to_cli_args() when fields change, else the runner
+/// will not properly forward arguments
+#[derive(Debug, Clone, Default, PartialEq, clap::Args, Serialize, Deserialize)]
+pub struct ProcessSettingsArgs {
+    /// CPUs the command is pinned to, to keep it from migrating between cores.
+    ///
+    /// Format: comma-separated list of CPUs and ranges of CPUs (e.g., "2", "0,2-3")
+    /// Default: undefined (all the CPUs)
+    #[arg(long, value_name = "CPUS")]
+    pub cpu_affinity: Option<String>,
+
+    /// Nice value the command runs with. Negative values require privileges, and are skipped
+    /// with a warning when not permitted.
+    ///
+    /// Format: integer between -20 and 19
+    /// Default: undefined (inherited)
+    #[arg(long, value_name = "NICE", allow_negative_numbers = true)]
+    pub nice: Option<i32>,
+
+    /// Real-time priority the command runs with, scheduled with the SCHED_FIFO policy so that
+    /// other processes don't preempt it. Requires privileges, and is skipped with a warning
+    /// when not permitted. Cannot be combined with --nice.
+    ///
+    /// Format: integer between 1 and 99
+    /// Default: undefined (inherited policy)
+    #[arg(long, value_name = "PRIORITY")]
+    pub sched_fifo: Option<u32>,
+
+    /// Disable the address space layout randomization of the command, like `setarch -R`, so
+    /// that the alignment of its memory doesn't change between executions
+    #[arg(long)]
+    #[serde(default)]
+    pub disable_aslr: bool,
+}
+
+impl ProcessSettingsArgs {
+    /// Convert ProcessSettingsArgs back to CLI argument strings
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if let Some(cpu_affinity) = &self.cpu_affinity {
+            args.push("--cpu-affinity".to_string());
+            args.push(cpu_affinity.clone());
+        }
+
+        if let Some(nice) = &self.nice {
+            args.push("--nice".to_string());
+            args.push(nice.to_string());
+        }
+
+        if let Some(sched_fifo) = &self.sched_fifo {
+            args.push("--sched-fifo".to_string());
+            args.push(sched_fifo.to_string());
+        }
+
+        if self.disable_aslr {
+            args.push("--disable-aslr".to_string());
+        }
+
+        args
+    }
+
+    /// Parse and validate the settings
+    pub fn to_settings(&self) -> Result<ProcessSettings> {
+        let cpu_affinity = self
+            .cpu_affinity
+            .as_ref()
+            .map(|s| parse_cpu_list(s))
+            .transpose()
+            .context("Invalid cpu_affinity")?;
+
+        if let Some(nice) = self.nice {
+            if !NICE_RANGE.contains(&nice) {
+                bail!(
+                    "nice must be between {} and {}",
+                    NICE_RANGE.start(),
+                    NICE_RANGE.end()
+                );
+            }
+        }
+
+        if let Some(priority) = self.sched_fifo {
+            if !SCHED_FIFO_PRIORITY_RANGE.contains(&priority) {
+                bail!(
+                    "sched_fifo must be between {} and {}",
+                    SCHED_FIFO_PRIORITY_RANGE.start(),
+                    SCHED_FIFO_PRIORITY_RANGE.end()
+                );
+            }
+            if self.nice.is_some() {
+                bail!(
+                    "nice and sched_fifo cannot be combined, the nice value is ignored by SCHED_FIFO"
+                );
+            }
+        }
+
+        Ok(ProcessSettings {
+            cpu_affinity,
+            nice: self.nice,
+            sched_fifo_priority: self.sched_fifo,
+            aslr_disabled: self.disable_aslr,
+        })
+    }
+}
+
+/// Parse a list of CPUs such as "0,2-3" into sorted and deduplicated CPU indices
+pub fn parse_cpu_list(s: &str) -> Result<Vec<usize>> {
+    let max_cpu = libc::CPU_SETSIZE as usize - 1;
+    let parse_cpu = |cpu: &str| -> Result<usize> {
+        let cpu = cpu.trim();
+        let cpu: usize = cpu.parse().with_context(|| {
+            format!("Invalid CPU '{cpu}' in '{s}'. Expected format like '2' or '0,2-3'")
+        })?;
+        if cpu > max_cpu {
+            bail!("CPU {cpu} is out of range, the maximum is {max_cpu}");
+        }
+        Ok(cpu)
+    };
+
+    let mut cpus = Vec::new();
+    for part in s.split(',') {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse_cpu(first)?, parse_cpu(last)?);
+                if first > last {
+                    bail!("Invalid range of CPUs '{}' in '{s}'", part.trim());
+                }
+                cpus.extend(first..=last);
+            }
+            None => cpus.push(parse_cpu(part)?),
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    Ok(cpus)
+}
+
+/// Apply the settings to the process when it is spawned, failing the spawn if they cannot be
+/// applied
+pub(crate) fn apply_process_settings(cmd: &mut Command, settings: &ProcessSettings) {
+    if settings == &ProcessSettings::default() {
+        return;
+    }
+
+    // The CPU set is built before forking, only async-signal-safe calls are made in the child
+    let cpu_set = settings.cpu_affinity.as_ref().map(|cpus| {
+        // SAFETY: cpu_set_t is a plain C struct for which zeroed memory is an empty set
+        let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for &cpu in cpus {
+            // SAFETY: the CPUs were checked to be below CPU_SETSIZE when parsed
+            unsafe { libc::CPU_SET(cpu, &mut cpu_set) };
+        }
+        cpu_set
+    });
+    let nice = settings.nice;
+    let sched_fifo_priority = settings.sched_fifo_priority;
+    let aslr_disabled = settings.aslr_disabled;
+
+    let check = |ret: libc::c_int| {
+        if ret == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    };
+
+    // SAFETY: the closure only makes syscalls, which are async-signal-safe
+    unsafe {
+        cmd.pre_exec(move || {
+            if let Some(cpu_set) = &cpu_set {
+                check(libc::sched_setaffinity(
+                    0,
+                    std::mem::size_of::<libc::cpu_set_t>(),
+                    cpu_set,
+                ))?;
+            }
+            if let Some(nice) = nice {
+                check(libc::setpriority(libc::PRIO_PROCESS, 0, nice))?;
+            }
+            if let Some(priority) = sched_fifo_priority {
+                let param = libc::sched_param {
+                    sched_priority: priority as libc::c_int,
+                };
+                check(libc::sched_setscheduler(0, libc::SCHED_FIFO, &param))?;
+            }
+            if aslr_disabled {
+                // Querying the current persona with 0xffffffff leaves it unchanged
+                let persona = libc::personality(0xffffffff);
+                check(persona)?;
+                check(libc::personality(
+                    (persona | libc::ADDR_NO_RANDOMIZE) as libc::c_ulong,
+                ))?;
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Check that the settings can be applied by spawning a no-op executable with them, dropping
+/// the privileged ones that are not permitted with a warning, e.g. SCHED_FIFO without
+/// CAP_SYS_NICE
+pub(crate) fn resolve_permitted_settings(
+    command: &BenchmarkCommand,
+    settings: &ProcessSettings,
+) -> Result<ProcessSettings> {
+    let mut settings = settings.clone();
+    if settings == ProcessSettings::default() {
+        return Ok(settings);
+    }
+
+    if let Some(priority) = settings.sched_fifo_priority {
+        let fifo_only = ProcessSettings {
+            sched_fifo_priority: Some(priority),
+            ..Default::default()
+        };
+        if !is_permitted(command, &fifo_only)? {
+            warn!(
+                "Not permitted to run the command with the SCHED_FIFO policy, running it with the inherited policy"
+            );
+            settings.sched_fifo_priority = None;
+        }
+    }
+
+    if let Some(nice) = settings.nice.filter(|&nice| nice < 0) {
+        let nice_only = ProcessSettings {
+            nice: Some(nice),
+            ..Default::default()
+        };
+        if !is_permitted(command, &nice_only)? {
+            warn!(
+                "Not permitted to run the command with the nice value {nice}, running it with the inherited one"
+            );
+            settings.nice = None;
+        }
+    }
+
+    if settings.aslr_disabled {
+        let aslr_only = ProcessSettings {
+            aslr_disabled: true,
+            ..Default::default()
+        };
+        if !is_permitted(command, &aslr_only)? {
+            warn!("Not permitted to disable the address space layout randomization of the command");
+            settings.aslr_disabled = false;
+        }
+    }
+
+    // The remaining settings, e.g. CPUs that are not available, must be applicable
+    spawn_noop(command, &settings).context("Failed to apply the process settings")?;
+
+    Ok(settings)
+}
+
+fn is_permitted(command: &BenchmarkCommand, settings: &ProcessSettings) -> Result<bool> {
+    match spawn_noop(command, settings) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn spawn_noop(command: &BenchmarkCommand, settings: &ProcessSettings) -> io::Result<()> {
+    let mut cmd = command.to_noop_process_command();
+    apply_process_settings(&mut cmd, settings);
+    cmd.status().map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("2").unwrap(), vec![2]);
+        assert_eq!(parse_cpu_list("0, 2-3").unwrap(), vec![0, 2, 3]);
+        assert_eq!(parse_cpu_list("3,1-3").unwrap(), vec![1, 2, 3]);
+
+        assert!(parse_cpu_list("").is_err());
+        assert!(parse_cpu_list("3-1").is_err());
+        assert!(parse_cpu_list("a").is_err());
+        assert!(parse_cpu_list("4096").is_err());
+    }
+
+    #[test]
+    fn test_to_settings_validation() {
+        let args = ProcessSettingsArgs {
+            cpu_affinity: Some("0-1".to_string()),
+            nice: Some(5),
+            sched_fifo: None,
+            disable_aslr: true,
+        };
+        assert_eq!(
+            args.to_settings().unwrap(),
+            ProcessSettings {
+                cpu_affinity: Some(vec![0, 1]),
+                nice: Some(5),
+                sched_fifo_priority: None,
+                aslr_disabled: true,
+            }
+        );
+
+        let is_invalid = |args: ProcessSettingsArgs| args.to_settings().is_err();
+        assert!(is_invalid(ProcessSettingsArgs {
+            nice: Some(20),
+            ..Default::default()
+        }));
+        assert!(is_invalid(ProcessSettingsArgs {
+            sched_fifo: Some(0),
+            ..Default::default()
+        }));
+        assert!(is_invalid(ProcessSettingsArgs {
+            nice: Some(-5),
+            sched_fifo: Some(10),
+            ..Default::default()
+        }));
+    }
+}
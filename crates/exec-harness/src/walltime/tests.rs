@@ -1,12 +1,21 @@
 use anyhow::Result;
 use tempfile::TempDir;
 
-use super::benchmark_loop::run_rounds;
+use super::benchmark_loop::{self, run_rounds};
 use super::*;
+use crate::assertions::BenchmarkAssertions;
+use crate::stdio::{BenchmarkStdio, OutputTarget};
+
+fn bench_cmd(command: Vec<String>) -> BenchmarkCommand {
+    BenchmarkCommand {
+        command,
+        ..Default::default()
+    }
+}
 
 // Helper to create a simple sleep 100ms command
-fn sleep_cmd() -> Vec<String> {
-    vec!["sleep".to_string(), "0.1".to_string()]
+fn sleep_cmd() -> BenchmarkCommand {
+    bench_cmd(vec!["sleep".to_string(), "0.1".to_string()])
 }
 
 /// Test that a command runs exactly the specified number of max_rounds
@@ -19,13 +28,18 @@ fn test_max_rounds_without_warmup() -> Result<()> {
         min_time: None,
         max_rounds: Some(10), // Exactly 10 rounds
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let times = run_rounds(
         "test::max_rounds_no_warmup".to_string(),
-        sleep_cmd(),
+        &sleep_cmd(),
         &exec_opts,
-    )?;
+    )?
+    .times_per_round_ns;
 
     // Should run exactly 10 times
     assert_eq!(times.len(), 10, "Expected exactly 10 iterations");
@@ -43,13 +57,18 @@ fn test_min_max_rounds_with_warmup() -> Result<()> {
         min_time: None,
         max_rounds: Some(50), // Max 50 rounds
         min_rounds: Some(5),  // Min 5 rounds
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let times = run_rounds(
         "test::min_max_rounds_warmup".to_string(),
-        sleep_cmd(),
+        &sleep_cmd(),
         &exec_opts,
-    )?;
+    )?
+    .times_per_round_ns;
 
     // Should run between 5 and 50 times
     assert!(
@@ -76,9 +95,14 @@ fn test_max_time_constraint() -> Result<()> {
         min_time: None,
         max_rounds: None,
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
-    let times = run_rounds("test::max_time".to_string(), sleep_cmd(), &exec_opts)?;
+    let times =
+        run_rounds("test::max_time".to_string(), &sleep_cmd(), &exec_opts)?.times_per_round_ns;
 
     // Should have run at least 1 time, but not an excessive amount
     assert!(!times.is_empty(), "Expected at least 1 iteration");
@@ -101,13 +125,18 @@ fn test_min_rounds_and_min_time() -> Result<()> {
         min_time: Some("1ms".to_string()),
         max_rounds: None,
         min_rounds: Some(15),
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let times = run_rounds(
         "test::min_rounds_priority".to_string(),
-        sleep_cmd(),
+        &sleep_cmd(),
         &exec_opts,
-    )?;
+    )?
+    .times_per_round_ns;
 
     // Should satisfy min_rounds requirement
     assert!(
@@ -129,11 +158,15 @@ fn test_warmup_is_performed() -> Result<()> {
         min_time: None,
         max_rounds: None,
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
-    let times_with_warmup = run_rounds(
+    let with_warmup = run_rounds(
         "test::with_warmup".to_string(),
-        sleep_cmd(),
+        &sleep_cmd(),
         &exec_opts_with_warmup,
     )?;
 
@@ -144,16 +177,22 @@ fn test_warmup_is_performed() -> Result<()> {
         min_time: None,
         max_rounds: Some(5), // Fixed 5 rounds
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let times_no_warmup = run_rounds(
         "test::no_warmup".to_string(),
-        sleep_cmd(),
+        &sleep_cmd(),
         &exec_opts_no_warmup,
-    )?;
+    )?
+    .times_per_round_ns;
 
     // Both should complete successfully
-    assert!(!times_with_warmup.is_empty());
+    assert!(!with_warmup.times_per_round_ns.is_empty());
+    assert!(with_warmup.warmup_iters > 0, "Expected warmup executions");
     assert_eq!(times_no_warmup.len(), 5);
 
     Ok(())
@@ -169,13 +208,18 @@ fn test_with_sleep_command() -> Result<()> {
         min_time: None,
         max_rounds: Some(3), // Just 3 rounds
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let times = run_rounds(
         "test::sleep_command".to_string(),
-        vec!["sleep".to_string(), "0.01".to_string()], // 10ms sleep
+        &bench_cmd(vec!["sleep".to_string(), "0.01".to_string()]), // 10ms sleep
         &exec_opts,
-    )?;
+    )?
+    .times_per_round_ns;
 
     // Should run exactly 3 times
     assert_eq!(times.len(), 3, "Expected exactly 3 iterations");
@@ -200,13 +244,19 @@ fn test_invalid_command_fails() {
         min_time: None,
         max_rounds: Some(5),
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })
     .unwrap();
 
     // Try to run a command that doesn't exist
     let result = run_rounds(
         "test::invalid_command".to_string(),
-        vec!["this_command_definitely_does_not_exist_12345".to_string()],
+        &bench_cmd(vec![
+            "this_command_definitely_does_not_exist_12345".to_string(),
+        ]),
         &exec_opts,
     );
 
@@ -224,13 +274,18 @@ fn test_pure_numbers_as_seconds() -> Result<()> {
         min_time: None,
         max_rounds: None,
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let times = run_rounds(
         "test::pure_numbers_seconds".to_string(),
-        sleep_cmd(),
+        &sleep_cmd(),
         &exec_opts,
-    )?;
+    )?
+    .times_per_round_ns;
 
     // Should have run at least once
     assert!(!times.is_empty(), "Expected at least one iteration");
@@ -242,13 +297,18 @@ fn test_pure_numbers_as_seconds() -> Result<()> {
         min_time: None,
         max_rounds: None,
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let times_fractional = run_rounds(
         "test::fractional_seconds".to_string(),
-        sleep_cmd(),
+        &sleep_cmd(),
         &exec_opts_fractional,
-    )?;
+    )?
+    .times_per_round_ns;
 
     assert!(
         !times_fractional.is_empty(),
@@ -268,6 +328,10 @@ fn test_single_long_execution() -> Result<()> {
         min_time: None,
         max_rounds: None,
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     // Create a temporary directory for the test
@@ -280,9 +344,10 @@ fn test_single_long_execution() -> Result<()> {
 
     let times = run_rounds(
         "test::single_long_execution".to_string(),
-        vec!["sh".to_string(), "-c".to_string(), cmd.clone()],
+        &bench_cmd(vec!["sh".to_string(), "-c".to_string(), cmd.clone()]),
         &exec_opts,
-    )?;
+    )?
+    .times_per_round_ns;
 
     // Should have run exactly once
     assert_eq!(times.len(), 1, "Expected exactly one iteration");
@@ -292,7 +357,7 @@ fn test_single_long_execution() -> Result<()> {
     assert!(
         run_rounds(
             "test::single_long_execution".to_string(),
-            vec!["sh".to_string(), "-c".to_string(), cmd],
+            &bench_cmd(vec!["sh".to_string(), "-c".to_string(), cmd]),
             &exec_opts,
         )
         .is_err(),
@@ -311,6 +376,10 @@ fn test_command_with_shell_operators() -> Result<()> {
         min_time: None,
         max_rounds: Some(1),
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let tmpdir = TempDir::new()?;
@@ -322,9 +391,10 @@ fn test_command_with_shell_operators() -> Result<()> {
 
     let times = run_rounds(
         "test::shell_operators".to_string(),
-        vec!["bash".to_string(), "-c".to_string(), cmd],
+        &bench_cmd(vec!["bash".to_string(), "-c".to_string(), cmd]),
         &exec_opts,
-    )?;
+    )?
+    .times_per_round_ns;
 
     assert_eq!(times.len(), 1, "Expected exactly 1 iteration");
 
@@ -353,6 +423,10 @@ fn test_command_with_pipes() -> Result<()> {
         min_time: None,
         max_rounds: Some(1),
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let tmpdir = TempDir::new()?;
@@ -366,9 +440,10 @@ fn test_command_with_pipes() -> Result<()> {
 
     let times = run_rounds(
         "test::pipes".to_string(),
-        vec!["bash".to_string(), "-c".to_string(), cmd],
+        &bench_cmd(vec!["bash".to_string(), "-c".to_string(), cmd]),
         &exec_opts,
-    )?;
+    )?
+    .times_per_round_ns;
 
     assert_eq!(times.len(), 1, "Expected exactly 1 iteration");
 
@@ -392,6 +467,10 @@ fn test_command_with_embedded_quotes() -> Result<()> {
         min_time: None,
         max_rounds: Some(1),
         min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
     })?;
 
     let tmpdir = TempDir::new()?;
@@ -402,9 +481,10 @@ fn test_command_with_embedded_quotes() -> Result<()> {
 
     let times = run_rounds(
         "test::embedded_quotes".to_string(),
-        vec!["bash".to_string(), "-c".to_string(), cmd],
+        &bench_cmd(vec!["bash".to_string(), "-c".to_string(), cmd]),
         &exec_opts,
-    )?;
+    )?
+    .times_per_round_ns;
 
     assert_eq!(times.len(), 1, "Expected exactly 1 iteration");
 
@@ -418,3 +498,659 @@ fn test_command_with_embedded_quotes() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that the command runs with its environment variables and working directory
+#[test]
+fn test_command_env_and_working_directory() -> Result<()> {
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(1),
+        min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
+    })?;
+
+    let tmpdir = TempDir::new()?;
+    let command = BenchmarkCommand {
+        command: vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo \"$DATASET\" > output.txt".to_string(),
+        ],
+        env: [("DATASET".to_string(), "large.json".to_string())].into(),
+        working_directory: Some(tmpdir.path().to_path_buf()),
+        ..Default::default()
+    };
+
+    let times =
+        run_rounds("test::env_and_cwd".to_string(), &command, &exec_opts)?.times_per_round_ns;
+    assert_eq!(times.len(), 1, "Expected exactly 1 iteration");
+
+    let content = std::fs::read_to_string(tmpdir.path().join("output.txt"))?;
+    assert_eq!(content.trim(), "large.json");
+
+    Ok(())
+}
+
+/// Test that a round exceeding the timeout is killed and fails the benchmark
+#[test]
+fn test_command_timeout() -> Result<()> {
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(3),
+        min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
+    })?;
+
+    let command = BenchmarkCommand {
+        command: vec!["sleep".to_string(), "10".to_string()],
+        timeout: Some("100ms".to_string()),
+        ..Default::default()
+    };
+
+    let start = std::time::Instant::now();
+    let result = run_rounds("test::timeout".to_string(), &command, &exec_opts);
+
+    let error = result.expect_err("Expected the round to be killed");
+    assert!(error.to_string().contains("timeout"), "{error}");
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+    Ok(())
+}
+
+/// Test that the hooks run around the rounds, and are not part of the measured time
+#[test]
+fn test_hooks_are_not_measured() -> Result<()> {
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(3),
+        min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
+    })?;
+
+    let tmpdir = TempDir::new()?;
+    let log_file = tmpdir.path().join("hooks.log");
+    let log = |event: &str| format!("echo {event} >> {}", log_file.display());
+    let command = BenchmarkCommand {
+        command: vec!["sh".to_string(), "-c".to_string(), log("round")],
+        hooks: crate::hooks::BenchmarkHooks {
+            setup: Some(log("setup")),
+            teardown: Some(log("teardown")),
+            before_each: Some(format!("sleep 0.2 && {}", log("before-each"))),
+            after_each: Some(format!("sleep 0.2 && {}", log("after-each"))),
+        },
+        ..Default::default()
+    };
+
+    let times = command
+        .with_setup_and_teardown(|| run_rounds("test::hooks".to_string(), &command, &exec_opts))?
+        .times_per_round_ns;
+    assert_eq!(times.len(), 3, "Expected exactly 3 iterations");
+    for time_ns in times {
+        assert!(
+            time_ns < 200_000_000,
+            "Hooks should not be measured, got {time_ns}ns"
+        );
+    }
+
+    let events = std::fs::read_to_string(&log_file)?;
+    let round = "before-each\nround\nafter-each\n";
+    assert_eq!(events, format!("setup\n{round}{round}{round}teardown\n"));
+
+    Ok(())
+}
+
+/// Test that the command is executed several times per round when requested
+#[test]
+fn test_iters_per_round() -> Result<()> {
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(3),
+        min_rounds: None,
+        iters_per_round: Some("4".to_string()),
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
+    })?;
+
+    let tmpdir = TempDir::new()?;
+    let log_file = tmpdir.path().join("executions.log");
+    let command = bench_cmd(vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        format!("sleep 0.01 && echo run >> {}", log_file.display()),
+    ]);
+
+    let measured = run_rounds("test::iters_per_round".to_string(), &command, &exec_opts)?;
+    assert_eq!(measured.iters_per_round, 4);
+    assert_eq!(
+        measured.times_per_round_ns.len(),
+        3,
+        "Expected exactly 3 rounds"
+    );
+    for time_ns in measured.times_per_round_ns {
+        assert!(
+            time_ns >= 40_000_000,
+            "A round of 4 executions took only {time_ns}ns, expected at least 40ms"
+        );
+    }
+
+    let executions = std::fs::read_to_string(&log_file)?.lines().count();
+    assert_eq!(executions, 12, "Expected 3 rounds of 4 executions");
+
+    Ok(())
+}
+
+/// Test that very short commands are batched during warmup with `auto`, and only then
+#[test]
+fn test_iters_per_round_chosen_during_warmup() -> Result<()> {
+    let exec_opts = |iters_per_round: Option<&str>| {
+        ExecutionOptions::try_from(WalltimeExecutionArgs {
+            warmup_time: Some("100ms".to_string()),
+            max_time: None,
+            min_time: None,
+            max_rounds: Some(3),
+            min_rounds: None,
+            iters_per_round: iters_per_round.map(str::to_string),
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
+        })
+    };
+
+    // A 100ms command is long enough to be measured alone
+    let measured = run_rounds(
+        "test::no_batching".to_string(),
+        &sleep_cmd(),
+        &exec_opts(Some("auto"))?,
+    )?;
+    assert_eq!(measured.iters_per_round, 1);
+
+    let measured = run_rounds(
+        "test::batching_disabled".to_string(),
+        &bench_cmd(vec!["true".to_string()]),
+        &exec_opts(None)?,
+    )?;
+    assert_eq!(measured.iters_per_round, 1);
+
+    let measured = run_rounds(
+        "test::batching".to_string(),
+        &bench_cmd(vec!["true".to_string()]),
+        &exec_opts(Some("auto"))?,
+    )?;
+    let time_per_iter_ns =
+        measured.times_per_round_ns.iter().sum::<u128>() / (3 * measured.iters_per_round) as u128;
+    assert!(
+        measured.iters_per_round > 1 || time_per_iter_ns >= 500_000,
+        "Expected a sub-millisecond command to be batched, got {} iterations of {time_per_iter_ns}ns",
+        measured.iters_per_round
+    );
+
+    Ok(())
+}
+
+/// Test that the per-round hooks run around the batch of executions of a round, and are left
+/// out of the warmup average choosing the iterations per round
+#[test]
+fn test_hooks_run_per_round() -> Result<()> {
+    let exec_opts = |warmup_time: &str, iters_per_round: &str| {
+        ExecutionOptions::try_from(WalltimeExecutionArgs {
+            warmup_time: Some(warmup_time.to_string()),
+            max_time: None,
+            min_time: None,
+            max_rounds: Some(3),
+            min_rounds: None,
+            iters_per_round: Some(iters_per_round.to_string()),
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
+        })
+    };
+
+    let tmpdir = TempDir::new()?;
+    let log_file = tmpdir.path().join("hooks.log");
+    let log = |event: &str| format!("echo {event} >> {}", log_file.display());
+    let command = BenchmarkCommand {
+        command: vec!["sh".to_string(), "-c".to_string(), log("run")],
+        hooks: crate::hooks::BenchmarkHooks {
+            before_each: Some(log("before-each")),
+            after_each: Some(log("after-each")),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    run_rounds(
+        "test::hooks_per_round".to_string(),
+        &command,
+        &exec_opts("0s", "2")?,
+    )?;
+    let events = std::fs::read_to_string(&log_file)?;
+    let round = "before-each\nrun\nrun\nafter-each\n";
+    assert_eq!(events, round.repeat(3));
+
+    // A slow hook does not prevent a sub-millisecond command from being batched
+    let command = BenchmarkCommand {
+        hooks: crate::hooks::BenchmarkHooks {
+            before_each: Some("sleep 0.01".to_string()),
+            ..Default::default()
+        },
+        ..bench_cmd(vec!["true".to_string()])
+    };
+    let measured = run_rounds(
+        "test::hooks_batching".to_string(),
+        &command,
+        &exec_opts("100ms", "auto")?,
+    )?;
+    let time_per_iter_ns =
+        measured.times_per_round_ns.iter().sum::<u128>() / (3 * measured.iters_per_round) as u128;
+    assert!(
+        measured.iters_per_round > 1 || time_per_iter_ns >= 500_000,
+        "Expected a sub-millisecond command to be batched, got {} iterations of {time_per_iter_ns}ns",
+        measured.iters_per_round
+    );
+
+    Ok(())
+}
+
+/// Test that the spawn overhead is calibrated and subtracted from the executions
+#[test]
+fn test_spawn_overhead_subtracted() -> Result<()> {
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(3),
+        min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: Some(SpawnOverheadMode::Subtract),
+        target_precision: None,
+        process_settings: Default::default(),
+    })?;
+
+    let measured = run_rounds(
+        "test::spawn_overhead".to_string(),
+        &bench_cmd(vec!["true".to_string()]),
+        &exec_opts,
+    )?;
+
+    let overhead = measured
+        .spawn_overhead
+        .expect("Expected the spawn overhead to be calibrated");
+    assert!(overhead.subtracted);
+    assert!(overhead.median_ns > 0.0);
+    // The no-op command costs about the calibrated overhead, which is removed from its rounds
+    let mean_ns = measured.times_per_round_ns.iter().sum::<u128>() as f64 / 3.0;
+    assert!(
+        mean_ns < overhead.median_ns,
+        "Expected the overhead ({}ns) to be subtracted, got a mean of {mean_ns}ns",
+        overhead.median_ns
+    );
+
+    Ok(())
+}
+
+/// Test that the resource usage of the measured executions is collected
+#[test]
+fn test_resource_usage() -> Result<()> {
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(3),
+        min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
+    })?;
+
+    let measured = run_rounds("test::resource_usage".to_string(), &sleep_cmd(), &exec_opts)?;
+
+    let usage = measured
+        .resource_usage
+        .expect("Expected the resource usage to be collected");
+    assert!(usage.max_rss_bytes > 0);
+    assert!(usage.minor_faults > 0.0);
+    // Sleeping blocks the process, which then gives up the CPU voluntarily
+    assert!(usage.voluntary_context_switches >= 1.0);
+
+    Ok(())
+}
+
+/// Test that the rounds stop once the target precision is reached
+#[test]
+fn test_target_precision() -> Result<()> {
+    let exec_opts = |min_rounds: Option<u64>| {
+        ExecutionOptions::try_from(WalltimeExecutionArgs {
+            warmup_time: Some("0s".to_string()),
+            max_time: None,
+            min_time: None,
+            max_rounds: Some(1000),
+            min_rounds,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: Some("50%".to_string()),
+            process_settings: Default::default(),
+        })
+    };
+    let command = bench_cmd(vec!["sleep".to_string(), "0.01".to_string()]);
+
+    // A sleep is stable enough to be within ±50% as soon as the precision is trusted
+    let measured = run_rounds(
+        "test::target_precision".to_string(),
+        &command,
+        &exec_opts(None)?,
+    )?;
+    let rounds = measured.times_per_round_ns.len();
+    assert!(
+        (5..1000).contains(&rounds),
+        "Expected the rounds to stop early once the precision is reached, got {rounds}"
+    );
+
+    // min_rounds is still honored
+    let measured = run_rounds(
+        "test::target_precision_min_rounds".to_string(),
+        &command,
+        &exec_opts(Some(20))?,
+    )?;
+    let rounds = measured.times_per_round_ns.len();
+    assert!(
+        (20..1000).contains(&rounds),
+        "Expected at least 20 rounds, got {rounds}"
+    );
+
+    Ok(())
+}
+
+/// Test that an unreachable target precision without a max bound stops at the default max_time
+#[test]
+fn test_target_precision_with_min_rounds_only() -> Result<()> {
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: None,
+        min_rounds: Some(10),
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: Some("0.0001%".to_string()),
+        process_settings: Default::default(),
+    })?;
+
+    let measured = run_rounds(
+        "test::target_precision_min_rounds_only".to_string(),
+        &bench_cmd(vec!["true".to_string()]),
+        &exec_opts,
+    )?;
+    let rounds = measured.times_per_round_ns.len();
+    assert!(rounds >= 10, "Expected at least 10 rounds, got {rounds}");
+
+    Ok(())
+}
+
+/// Test that interleaved benchmarks each run their own rounds, one round of each in turn
+#[test]
+fn test_interleaved_rounds() -> Result<()> {
+    let tmpdir = TempDir::new()?;
+    let log = tmpdir.path().join("log");
+    let exec_opts = |max_rounds: u64| {
+        ExecutionOptions::try_from(WalltimeExecutionArgs {
+            warmup_time: Some("0s".to_string()),
+            max_time: None,
+            min_time: None,
+            max_rounds: Some(max_rounds),
+            min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
+        })
+    };
+    let (opts_a, opts_b) = (exec_opts(3)?, exec_opts(5)?);
+    let logging_cmd = |name: &str| {
+        bench_cmd(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("echo {name} >> {}", log.display()),
+        ])
+    };
+    let (cmd_a, cmd_b) = (logging_cmd("a"), logging_cmd("b"));
+
+    let run = |shuffle_seed: Option<u64>| -> Result<(Vec<MeasuredRounds>, String)> {
+        std::fs::write(&log, "")?;
+        let benchmarks = vec![
+            BenchmarkRounds::new("test::interleaved_a".to_string(), &cmd_a, &opts_a)?,
+            BenchmarkRounds::new("test::interleaved_b".to_string(), &cmd_b, &opts_b)?,
+        ];
+        let measured = benchmark_loop::run_interleaved_rounds(benchmarks, shuffle_seed)?;
+        Ok((measured, std::fs::read_to_string(&log)?.replace('\n', "")))
+    };
+
+    let (measured, order) = run(None)?;
+    assert_eq!(measured[0].times_per_round_ns.len(), 3);
+    assert_eq!(measured[1].times_per_round_ns.len(), 5);
+    assert_eq!(order, "abababbb");
+
+    // The shuffled order is reproducible from its seed
+    let (measured, shuffled_order) = run(Some(42))?;
+    assert_eq!(measured[0].times_per_round_ns.len(), 3);
+    assert_eq!(measured[1].times_per_round_ns.len(), 5);
+    assert_eq!(shuffled_order.matches('a').count(), 3);
+    assert_eq!(run(Some(42))?.1, shuffled_order);
+
+    Ok(())
+}
+
+/// Test that the exit code and the output of the command are checked
+#[test]
+fn test_assertions() -> Result<()> {
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(3),
+        min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
+    })?;
+    let linter_cmd = |assertions: BenchmarkAssertions| BenchmarkCommand {
+        assertions,
+        ..bench_cmd(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo '2 issues found'; exit 1".to_string(),
+        ])
+    };
+
+    // A non-zero exit code fails by default
+    let result = run_rounds(
+        "test::assertions_default".to_string(),
+        &linter_cmd(BenchmarkAssertions::default()),
+        &exec_opts,
+    );
+    assert!(result.is_err(), "Expected error for non-zero exit code");
+
+    let times = run_rounds(
+        "test::assertions_expected".to_string(),
+        &linter_cmd(BenchmarkAssertions {
+            expect_exit_code: Some(1),
+            expect_stdout_regex: Some(r"\d+ issues found".to_string()),
+            ..Default::default()
+        }),
+        &exec_opts,
+    )?
+    .times_per_round_ns;
+    assert_eq!(times.len(), 3);
+
+    let result = run_rounds(
+        "test::assertions_unexpected_output".to_string(),
+        &linter_cmd(BenchmarkAssertions {
+            expect_exit_code: Some(1),
+            expect_stdout_regex: Some("no issues".to_string()),
+            ..Default::default()
+        }),
+        &exec_opts,
+    );
+    assert!(result.is_err(), "Expected error for unexpected output");
+
+    Ok(())
+}
+
+/// Test that the output is checked on an execution run before the measured rounds
+#[test]
+fn test_output_checked_before_rounds() -> Result<()> {
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(3),
+        min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
+    })?;
+
+    let tmpdir = TempDir::new()?;
+    let log_file = tmpdir.path().join("executions.log");
+    let command = BenchmarkCommand {
+        assertions: BenchmarkAssertions {
+            expect_stdout_regex: Some("^run".to_string()),
+            ..Default::default()
+        },
+        ..bench_cmd(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("echo run | tee -a {}", log_file.display()),
+        ])
+    };
+
+    let times =
+        run_rounds("test::output_checked".to_string(), &command, &exec_opts)?.times_per_round_ns;
+    assert_eq!(times.len(), 3);
+    let executions = std::fs::read_to_string(&log_file)?.lines().count();
+    assert_eq!(
+        executions, 4,
+        "Expected the checked execution before 3 rounds"
+    );
+
+    Ok(())
+}
+
+/// Test that the stdin file is fed to every execution and that the output can be discarded
+#[test]
+fn test_stdio_redirections() -> Result<()> {
+    let tmpdir = TempDir::new()?;
+    let input = tmpdir.path().join("input.txt");
+    std::fs::write(&input, "hello\n")?;
+
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(3),
+        min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: Default::default(),
+    })?;
+    let command = BenchmarkCommand {
+        stdio: BenchmarkStdio {
+            stdin: Some(input),
+            stdout: Some(OutputTarget::Null),
+            stderr: Some(OutputTarget::Null),
+        },
+        // The discarded output is still checked
+        assertions: BenchmarkAssertions {
+            expect_stdout_regex: Some("^got hello".to_string()),
+            ..Default::default()
+        },
+        ..bench_cmd(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "read line && test \"$line\" = hello && echo \"got $line\"".to_string(),
+        ])
+    };
+
+    let times = run_rounds("test::stdio".to_string(), &command, &exec_opts)?.times_per_round_ns;
+    assert_eq!(times.len(), 3);
+
+    Ok(())
+}
+
+/// Test that the executions are pinned, niced and run without ASLR, and that the settings are
+/// recorded
+#[test]
+fn test_process_settings() -> Result<()> {
+    // Pin to one of the CPUs the tests are allowed to run on
+    let status = std::fs::read_to_string("/proc/self/status")?;
+    let allowed_cpus = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+        .expect("Cpus_allowed_list in /proc/self/status");
+    let cpu = parse_cpu_list(allowed_cpus)?[0];
+
+    let exec_opts = ExecutionOptions::try_from(WalltimeExecutionArgs {
+        warmup_time: Some("0s".to_string()),
+        max_time: None,
+        min_time: None,
+        max_rounds: Some(3),
+        min_rounds: None,
+        iters_per_round: None,
+        spawn_overhead: None,
+        target_precision: None,
+        process_settings: ProcessSettingsArgs {
+            cpu_affinity: Some(cpu.to_string()),
+            nice: Some(5),
+            sched_fifo: None,
+            disable_aslr: true,
+        },
+    })?;
+    let command = BenchmarkCommand {
+        stdio: BenchmarkStdio {
+            stdout: Some(OutputTarget::Null),
+            ..Default::default()
+        },
+        assertions: BenchmarkAssertions {
+            expect_stdout_regex: Some(format!(r"Cpus_allowed_list:\s+{cpu}\n5\n00040000")),
+            ..Default::default()
+        },
+        ..bench_cmd(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "grep Cpus_allowed_list /proc/self/status; nice; cat /proc/self/personality"
+                .to_string(),
+        ])
+    };
+
+    let measured = run_rounds("test::process_settings".to_string(), &command, &exec_opts)?;
+    assert_eq!(measured.times_per_round_ns.len(), 3);
+    let settings = measured
+        .process_settings
+        .expect("recorded process settings");
+    assert_eq!(settings.cpu_affinity, Some(vec![cpu]));
+    assert_eq!(settings.nice, Some(5));
+    assert!(settings.aslr_disabled);
+
+    Ok(())
+}
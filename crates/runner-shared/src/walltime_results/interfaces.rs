@@ -36,6 +36,57 @@ pub(super) struct BenchmarkConfig {
     pub max_rounds: Option<u64>,
 }
 
+/// Process spawn overhead calibrated before running the rounds of a benchmark
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpawnOverhead {
+    /// Median time of an execution of a no-op executable
+    pub median_ns: f64,
+    /// Whether the overhead was subtracted from the time of each execution
+    pub subtracted: bool,
+}
+
+/// Precision of the mean targeted by a benchmark, which ran rounds until reaching it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Precision {
+    /// Targeted relative half-width of the 95% confidence interval of the mean, e.g. `0.01`
+    pub target: f64,
+    /// Relative half-width of the confidence interval of the mean once the rounds stopped,
+    /// undefined with fewer than 2 rounds
+    pub achieved: Option<f64>,
+}
+
+/// Resources used by the executions of a benchmark, as reported by the kernel when reaping them
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ResourceUsage {
+    /// Mean CPU time spent in user mode per execution
+    pub user_time_ns: f64,
+    /// Mean CPU time spent in kernel mode per execution
+    pub system_time_ns: f64,
+    /// Largest resident set size reached by an execution
+    pub max_rss_bytes: u64,
+    /// Mean page faults serviced without I/O per execution
+    pub minor_faults: f64,
+    /// Mean page faults that required I/O per execution
+    pub major_faults: f64,
+    /// Mean voluntary context switches per execution
+    pub voluntary_context_switches: f64,
+    /// Mean involuntary context switches per execution
+    pub involuntary_context_switches: f64,
+}
+
+/// Scheduling and address space settings the executions of a benchmark ran with
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ProcessSettings {
+    /// CPUs the executions were pinned to
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// Nice value of the executions
+    pub nice: Option<i32>,
+    /// Real-time priority of the executions, scheduled with the SCHED_FIFO policy
+    pub sched_fifo_priority: Option<u32>,
+    /// Whether the address space layout randomization was disabled
+    pub aslr_disabled: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WalltimeBenchmark {
     #[serde(flatten)]
@@ -43,6 +94,18 @@ pub struct WalltimeBenchmark {
 
     pub(super) config: BenchmarkConfig,
     pub stats: BenchmarkStats,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawn_overhead: Option<SpawnOverhead>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_usage: Option<ResourceUsage>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<Precision>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_settings: Option<ProcessSettings>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -58,9 +121,19 @@ pub struct Creator {
     pub pid: u32,
 }
 
+/// Scheduling of the rounds of benchmarks interleaved with each other
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Interleaving {
+    /// Seed of the shuffled order of the rounds, `None` if they ran in the order of the benchmarks
+    pub shuffle_seed: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WalltimeResults {
     pub creator: Creator,
     pub instrument: Instrument,
     pub benchmarks: Vec<WalltimeBenchmark>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interleaving: Option<Interleaving>,
 }
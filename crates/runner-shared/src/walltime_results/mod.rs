@@ -2,6 +2,7 @@ mod interfaces;
 mod stats;
 
 pub use interfaces::*;
+pub use stats::relative_precision;
 
 impl WalltimeResults {
     pub fn new(creator: Creator, benchmarks: Vec<WalltimeBenchmark>) -> anyhow::Result<Self> {
@@ -11,6 +12,7 @@ impl WalltimeResults {
             },
             creator,
             benchmarks,
+            interleaving: None,
         })
     }
 
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-use super::{BenchmarkConfig, BenchmarkMetadata, BenchmarkStats, WalltimeBenchmark};
+use super::{BenchmarkConfig, BenchmarkMetadata, BenchmarkStats, ResourceUsage, WalltimeBenchmark};
 
 impl WalltimeBenchmark {
     /// Create a WalltimeBenchmark from runtime data.
@@ -98,10 +98,64 @@ impl WalltimeBenchmark {
                 iter_per_round,
                 warmup_iters: 0,
             },
+            spawn_overhead: None,
+            resource_usage: None,
+            precision: None,
+            process_settings: None,
         }
     }
 }
 
+impl BenchmarkStats {
+    /// Relative precision of the mean of the rounds, see [`relative_precision`]
+    pub fn relative_precision(&self) -> Option<f64> {
+        relative_precision(self.mean_ns, self.stdev_ns, self.rounds)
+    }
+}
+
+/// z-score of the two-sided 95% confidence interval
+const CONFIDENCE_Z_SCORE: f64 = 1.96;
+
+/// Relative half-width of the 95% confidence interval of a mean, e.g. `0.01` when the true mean
+/// is within ±1% of the measured one.
+///
+/// Returns `None` with fewer than 2 samples or a zero mean.
+pub fn relative_precision(mean: f64, stdev: f64, samples: u64) -> Option<f64> {
+    if samples < 2 || mean == 0.0 {
+        return None;
+    }
+    Some(CONFIDENCE_Z_SCORE * stdev / (samples as f64).sqrt() / mean)
+}
+
+impl ResourceUsage {
+    /// Aggregate the resources used by each execution of a benchmark: the maximum RSS is the
+    /// largest of all executions, the other values are averaged over the executions.
+    ///
+    /// Returns `None` if there are no executions.
+    pub fn aggregate(executions: &[ResourceUsage]) -> Option<Self> {
+        if executions.is_empty() {
+            return None;
+        }
+
+        let mean = |value: fn(&ResourceUsage) -> f64| {
+            executions.iter().map(value).sum::<f64>() / executions.len() as f64
+        };
+        Some(Self {
+            user_time_ns: mean(|usage| usage.user_time_ns),
+            system_time_ns: mean(|usage| usage.system_time_ns),
+            max_rss_bytes: executions
+                .iter()
+                .map(|usage| usage.max_rss_bytes)
+                .max()
+                .unwrap_or_default(),
+            minor_faults: mean(|usage| usage.minor_faults),
+            major_faults: mean(|usage| usage.major_faults),
+            voluntary_context_switches: mean(|usage| usage.voluntary_context_switches),
+            involuntary_context_switches: mean(|usage| usage.involuntary_context_switches),
+        })
+    }
+}
+
 /// Calculate sample standard deviation (n-1 denominator).
 /// This is intended to match pytest-codspeed's computation, which uses python's
 /// statistics.stdev
@@ -196,6 +250,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_aggregate_resource_usage() {
+        assert_eq!(ResourceUsage::aggregate(&[]), None);
+
+        let executions = [
+            ResourceUsage {
+                user_time_ns: 1000.,
+                system_time_ns: 200.,
+                max_rss_bytes: 4096,
+                minor_faults: 10.,
+                major_faults: 0.,
+                voluntary_context_switches: 1.,
+                involuntary_context_switches: 3.,
+            },
+            ResourceUsage {
+                user_time_ns: 3000.,
+                system_time_ns: 400.,
+                max_rss_bytes: 8192,
+                minor_faults: 20.,
+                major_faults: 2.,
+                voluntary_context_switches: 3.,
+                involuntary_context_switches: 1.,
+            },
+        ];
+        assert_eq!(
+            ResourceUsage::aggregate(&executions),
+            Some(ResourceUsage {
+                user_time_ns: 2000.,
+                system_time_ns: 300.,
+                max_rss_bytes: 8192,
+                minor_faults: 15.,
+                major_faults: 1.,
+                voluntary_context_switches: 2.,
+                involuntary_context_switches: 2.,
+            })
+        );
+    }
+
+    #[test]
+    fn test_relative_precision() {
+        assert_eq!(relative_precision(100.0, 10.0, 1), None);
+        assert_eq!(relative_precision(0.0, 10.0, 10), None);
+
+        // 1.96 * 10 / sqrt(16) / 100
+        let precision = relative_precision(100.0, 10.0, 16).unwrap();
+        assert!((precision - 0.049).abs() < 1e-9);
+        // Quadrupling the samples halves the interval
+        let precision = relative_precision(100.0, 10.0, 64).unwrap();
+        assert!((precision - 0.0245).abs() < 1e-9);
+    }
+
     #[test]
     fn test_basic_stats_computation() {
         // Test with a simple benchmark with consistent iterations
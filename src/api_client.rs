@@ -236,6 +236,16 @@ nest! {
     }
 }
 
+nest! {
+    #[derive(Debug, Deserialize, Serialize)]*
+    #[serde(rename_all = "camelCase")]*
+    struct GetAuthenticatedUserData {
+        me: pub struct AuthenticatedUser {
+            pub login: String,
+        }
+    }
+}
+
 impl CodSpeedAPIClient {
     pub async fn create_login_session(&self) -> Result<CreateLoginSessionPayload> {
         let response = self
@@ -267,6 +277,22 @@ impl CodSpeedAPIClient {
         }
     }
 
+    /// Fetch the user the token is associated with.
+    /// Returns None if the token is missing, invalid or expired.
+    pub async fn get_authenticated_user(&self) -> Result<Option<AuthenticatedUser>> {
+        let response = self
+            .gql_client
+            .query_unwrap::<GetAuthenticatedUserData>(include_str!(
+                "queries/GetAuthenticatedUser.gql"
+            ))
+            .await;
+        match response {
+            Ok(response) => Ok(Some(response.me)),
+            Err(err) if err.contains_error_code("UNAUTHENTICATED") => Ok(None),
+            Err(err) => bail!("Failed to fetch the authenticated user: {err}"),
+        }
+    }
+
     pub async fn fetch_local_run_report(
         &self,
         vars: FetchLocalRunReportVars,
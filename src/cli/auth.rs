@@ -1,6 +1,11 @@
+use std::io::BufRead;
 use std::time::Duration;
 
-use crate::{api_client::CodSpeedAPIClient, config::CodSpeedConfig, prelude::*};
+use crate::{
+    api_client::CodSpeedAPIClient,
+    config::{CodSpeedConfig, get_configuration_file_path},
+    prelude::*,
+};
 use clap::{Args, Subcommand};
 use console::style;
 use tokio::time::{Instant, sleep};
@@ -14,7 +19,23 @@ pub struct AuthArgs {
 #[derive(Debug, Subcommand)]
 enum AuthCommands {
     /// Login to CodSpeed
-    Login,
+    Login(LoginArgs),
+    /// Remove the token stored in the configuration file
+    Logout,
+    /// Check that the stored token is valid and show the active configuration
+    Status,
+}
+
+#[derive(Debug, Args)]
+struct LoginArgs {
+    /// Store the given token instead of opening a browser to complete the login, useful on
+    /// headless machines
+    #[arg(long, conflicts_with = "token_stdin")]
+    token: Option<String>,
+
+    /// Read the token to store from the standard input
+    #[arg(long)]
+    token_stdin: bool,
 }
 
 pub async fn run(
@@ -23,14 +44,50 @@ pub async fn run(
     config_name: Option<&str>,
 ) -> Result<()> {
     match args.command {
-        AuthCommands::Login => login(api_client, config_name).await?,
+        AuthCommands::Login(args) => login(args, api_client, config_name).await?,
+        AuthCommands::Logout => logout(config_name)?,
+        AuthCommands::Status => status(api_client, config_name).await?,
     }
     Ok(())
 }
 
 const LOGIN_SESSION_MAX_DURATION: Duration = Duration::from_secs(60 * 5); // 5 minutes
 
-async fn login(api_client: &CodSpeedAPIClient, config_name: Option<&str>) -> Result<()> {
+async fn login(
+    args: LoginArgs,
+    api_client: &CodSpeedAPIClient,
+    config_name: Option<&str>,
+) -> Result<()> {
+    let token = match args.token {
+        Some(token) => token,
+        None if args.token_stdin => read_token(std::io::stdin().lock())?,
+        None => login_with_browser(api_client).await?,
+    };
+
+    let mut config = CodSpeedConfig::load_with_override(config_name, None)?;
+    config.auth.token = Some(token);
+    config.persist(config_name)?;
+    debug!("Token saved to configuration file");
+
+    info!("Login successful, your are now authenticated on CodSpeed");
+
+    Ok(())
+}
+
+/// Read a token from the first line of the reader
+fn read_token(mut reader: impl BufRead) -> Result<String> {
+    let mut token = String::new();
+    reader
+        .read_line(&mut token)
+        .context("Failed to read the token from stdin")?;
+    let token = token.trim();
+    if token.is_empty() {
+        bail!("No token provided on stdin");
+    }
+    Ok(token.to_string())
+}
+
+async fn login_with_browser(api_client: &CodSpeedAPIClient) -> Result<String> {
     debug!("Login to CodSpeed");
     start_group!("Creating login session");
     let login_session_payload = api_client.create_login_session().await?;
@@ -71,12 +128,58 @@ async fn login(api_client: &CodSpeedAPIClient, config_name: Option<&str>) -> Res
     }
     end_group!();
 
+    Ok(token)
+}
+
+fn logout(config_name: Option<&str>) -> Result<()> {
     let mut config = CodSpeedConfig::load_with_override(config_name, None)?;
-    config.auth.token = Some(token);
+    if config.auth.token.take().is_none() {
+        info!("You are not logged in");
+        return Ok(());
+    }
     config.persist(config_name)?;
-    debug!("Token saved to configuration file");
+    debug!("Token removed from configuration file");
 
-    info!("Login successful, your are now authenticated on CodSpeed");
+    info!("Logout successful");
 
     Ok(())
 }
+
+async fn status(api_client: &CodSpeedAPIClient, config_name: Option<&str>) -> Result<()> {
+    info!(
+        "Configuration: {} ({})",
+        style(config_name.unwrap_or("default")).bold(),
+        get_configuration_file_path(config_name).display()
+    );
+
+    let config = CodSpeedConfig::load_with_override(config_name, None)?;
+    if config.auth.token.is_none() && std::env::var_os("CODSPEED_OAUTH_TOKEN").is_none() {
+        bail!("You are not logged in. Run `codspeed auth login` to authenticate.");
+    }
+
+    match api_client.get_authenticated_user().await? {
+        Some(user) => {
+            info!("Logged in to CodSpeed as {}", style(user.login).bold());
+            Ok(())
+        }
+        None => bail!(
+            "The stored token is invalid or has expired. Run `codspeed auth login` to authenticate again."
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_token() {
+        assert_eq!(read_token("my-token\n".as_bytes()).unwrap(), "my-token");
+        assert_eq!(
+            read_token("  my-token  \nignored".as_bytes()).unwrap(),
+            "my-token"
+        );
+        assert!(read_token("\n".as_bytes()).is_err());
+        assert!(read_token("".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::cli::run::helpers;
+use crate::cli::run::helpers::benchmark_display::build_table_with_style;
+use crate::prelude::*;
+use clap::Args;
+use console::style;
+use std::path::PathBuf;
+use tabled::Tabled;
+
+pub(super) mod profile_results;
+mod significance;
+
+use profile_results::{MemoryEntry, ProfileResults, WalltimeEntry};
+
+#[derive(Debug, Args)]
+pub struct CompareArgs {
+    /// Profile folder containing the baseline results
+    pub base: PathBuf,
+
+    /// Profile folder containing the results to compare against the baseline
+    pub head: PathBuf,
+
+    /// Significance level of the Welch's t-test used to decide whether a walltime change is
+    /// significant or due to noise
+    #[arg(long, default_value_t = 0.05)]
+    pub significance_level: f64,
+}
+
+pub fn run(args: CompareArgs) -> Result<()> {
+    if !(0.0..1.0).contains(&args.significance_level) {
+        bail!("The significance level must be between 0 and 1");
+    }
+
+    let base = ProfileResults::load(&args.base)
+        .with_context(|| format!("Failed to load results from {}", args.base.display()))?;
+    let head = ProfileResults::load(&args.head)
+        .with_context(|| format!("Failed to load results from {}", args.head.display()))?;
+
+    let mut output = String::new();
+    if !base.walltime.is_empty() || !head.walltime.is_empty() {
+        let rows = build_walltime_rows(&base.walltime, &head.walltime, args.significance_level);
+        output.push_str(&build_table_with_style(&rows, "Walltime"));
+    }
+    if !base.memory.is_empty() || !head.memory.is_empty() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        let rows = build_memory_rows(&base.memory, &head.memory);
+        output.push_str(&build_table_with_style(&rows, "Memory"));
+    }
+
+    info!("\n{output}");
+
+    Ok(())
+}
+
+#[derive(Tabled)]
+struct WalltimeComparisonRow {
+    #[tabled(rename = "Benchmark")]
+    name: String,
+    #[tabled(rename = "Base (mean)")]
+    base: String,
+    #[tabled(rename = "Head (mean)")]
+    head: String,
+    #[tabled(rename = "Change")]
+    change: String,
+    #[tabled(rename = "p-value")]
+    p_value: String,
+}
+
+#[derive(Tabled)]
+struct MemoryComparisonRow {
+    #[tabled(rename = "Benchmark")]
+    name: String,
+    #[tabled(rename = "Base (peak)")]
+    base: String,
+    #[tabled(rename = "Head (peak)")]
+    head: String,
+    #[tabled(rename = "Change")]
+    change: String,
+    #[tabled(rename = "Allocations")]
+    alloc_calls: String,
+}
+
+/// Pairs the benchmarks of both sides by URI, keeping the head order and appending the
+/// benchmarks that only exist in the base.
+fn pair_by_uri<'a, T>(
+    base: &'a [T],
+    head: &'a [T],
+    uri: impl Fn(&T) -> &str,
+) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+    let mut pairs: Vec<_> = head
+        .iter()
+        .map(|h| (base.iter().find(|b| uri(b) == uri(h)), Some(h)))
+        .collect();
+    pairs.extend(
+        base.iter()
+            .filter(|b| !head.iter().any(|h| uri(h) == uri(b)))
+            .map(|b| (Some(b), None)),
+    );
+    pairs
+}
+
+fn relative_change(base: f64, head: f64) -> f64 {
+    (head - base) / base
+}
+
+fn format_relative_change(change: f64, is_significant: bool) -> String {
+    let text = format!("{:+.2}%", change * 100.0);
+    match (is_significant, change > 0.0) {
+        (false, _) => text,
+        (true, true) => style(text).red().bold().to_string(),
+        (true, false) => style(text).green().bold().to_string(),
+    }
+}
+
+fn build_walltime_rows(
+    base: &[WalltimeEntry],
+    head: &[WalltimeEntry],
+    significance_level: f64,
+) -> Vec<WalltimeComparisonRow> {
+    let format_mean = |entry: Option<&WalltimeEntry>| {
+        entry
+            .map(|e| helpers::format_duration(e.stats.mean_ns / 1_000_000_000.0, Some(2)))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    pair_by_uri(base, head, |e| e.uri.as_str())
+        .into_iter()
+        .map(|(b, h)| {
+            let name = h.or(b).map(|e| e.name.clone()).unwrap_or_default();
+            let (change, p_value) = match (b, h) {
+                (Some(b), Some(h)) => {
+                    let p_value = significance::welch_t_test(&b.stats, &h.stats);
+                    let is_significant = p_value.is_some_and(|p| p < significance_level);
+                    (
+                        format_relative_change(
+                            relative_change(b.stats.mean_ns, h.stats.mean_ns),
+                            is_significant,
+                        ),
+                        p_value
+                            .map(|p| format!("{p:.3}"))
+                            .unwrap_or_else(|| "-".to_string()),
+                    )
+                }
+                (None, _) => ("added".to_string(), "-".to_string()),
+                (_, None) => ("removed".to_string(), "-".to_string()),
+            };
+
+            WalltimeComparisonRow {
+                name,
+                base: format_mean(b),
+                head: format_mean(h),
+                change,
+                p_value,
+            }
+        })
+        .collect()
+}
+
+fn build_memory_rows(base: &[MemoryEntry], head: &[MemoryEntry]) -> Vec<MemoryComparisonRow> {
+    let format_peak = |entry: Option<&MemoryEntry>| {
+        entry
+            .map(|e| helpers::format_memory(e.peak_memory as f64, Some(1)))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    pair_by_uri(base, head, |e| e.uri.as_str())
+        .into_iter()
+        .map(|(b, h)| {
+            let name = h.or(b).map(|e| e.uri.clone()).unwrap_or_default();
+            let (change, alloc_calls) = match (b, h) {
+                (Some(b), Some(h)) => (
+                    // Memory results are deterministic, any change is significant
+                    format_relative_change(
+                        relative_change(b.peak_memory as f64, h.peak_memory as f64),
+                        b.peak_memory != h.peak_memory,
+                    ),
+                    format!("{} → {}", b.alloc_calls, h.alloc_calls),
+                ),
+                (None, Some(h)) => ("added".to_string(), h.alloc_calls.to_string()),
+                (Some(b), None) => ("removed".to_string(), b.alloc_calls.to_string()),
+                (None, None) => unreachable!(),
+            };
+
+            MemoryComparisonRow {
+                name,
+                base: format_peak(b),
+                head: format_peak(h),
+                change,
+                alloc_calls,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use runner_shared::walltime_results::BenchmarkStats;
+
+    fn walltime_entry(uri: &str, mean_ns: f64, stdev_ns: f64) -> WalltimeEntry {
+        WalltimeEntry {
+            name: uri.to_string(),
+            uri: uri.to_string(),
+            stats: BenchmarkStats {
+                min_ns: mean_ns,
+                max_ns: mean_ns,
+                mean_ns,
+                stdev_ns,
+                q1_ns: mean_ns,
+                median_ns: mean_ns,
+                q3_ns: mean_ns,
+                rounds: 100,
+                total_time: mean_ns * 100.0 / 1_000_000_000.0,
+                iqr_outlier_rounds: 0,
+                stdev_outlier_rounds: 0,
+                iter_per_round: 1,
+                warmup_iters: 0,
+            },
+            resource_usage: None,
+        }
+    }
+
+    #[test]
+    fn test_pair_by_uri() {
+        let base = vec![walltime_entry("a", 1.0, 0.0), walltime_entry("b", 1.0, 0.0)];
+        let head = vec![walltime_entry("c", 1.0, 0.0), walltime_entry("a", 1.0, 0.0)];
+
+        let pairs: Vec<_> = pair_by_uri(&base, &head, |e| e.uri.as_str())
+            .into_iter()
+            .map(|(b, h)| (b.map(|e| e.uri.as_str()), h.map(|e| e.uri.as_str())))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![(None, Some("c")), (Some("a"), Some("a")), (Some("b"), None)]
+        );
+    }
+
+    #[test]
+    fn test_walltime_rows() {
+        let base = vec![
+            walltime_entry("stable", 1_000_000.0, 100_000.0),
+            walltime_entry("regressed", 1_000_000.0, 1_000.0),
+            walltime_entry("removed", 1_000_000.0, 1_000.0),
+        ];
+        let head = vec![
+            walltime_entry("stable", 1_010_000.0, 100_000.0),
+            walltime_entry("regressed", 1_500_000.0, 1_000.0),
+        ];
+
+        let rows = build_walltime_rows(&base, &head, 0.05);
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|r| {
+                (
+                    r.name.as_str(),
+                    console::strip_ansi_codes(&r.change).to_string(),
+                    r.p_value.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("stable", "+1.00%".to_string(), "0.480"),
+                ("regressed", "+50.00%".to_string(), "0.000"),
+                ("removed", "removed".to_string(), "-"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_memory_rows() {
+        let entry = |uri: &str, peak_memory| MemoryEntry {
+            uri: uri.to_string(),
+            peak_memory,
+            total_allocated: peak_memory,
+            alloc_calls: 10,
+        };
+        let rows = build_memory_rows(&[entry("a", 1024)], &[entry("a", 2048), entry("b", 10)]);
+
+        assert_eq!(console::strip_ansi_codes(&rows[0].change), "+100.00%");
+        assert_eq!(rows[0].alloc_calls, "10 → 10");
+        assert_eq!(rows[1].change, "added");
+    }
+}
@@ -0,0 +1,333 @@
+use crate::prelude::*;
+use runner_shared::artifacts::{
+    ArtifactExt, ExecutionTimestamps, MemtrackArtifact, MemtrackEvent, MemtrackEventKind,
+};
+use runner_shared::fifo::MarkerType;
+use runner_shared::walltime_results::{BenchmarkStats, ResourceUsage, WalltimeResults};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A walltime benchmark loaded from a `results/*.json` file of a profile folder
+#[derive(Debug, Clone)]
+pub struct WalltimeEntry {
+    pub name: String,
+    pub uri: String,
+    pub stats: BenchmarkStats,
+    pub resource_usage: Option<ResourceUsage>,
+}
+
+/// Heap usage of a benchmark, computed from the memtrack events recorded within its
+/// `BenchmarkStart`/`BenchmarkEnd` markers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryEntry {
+    pub uri: String,
+    pub peak_memory: u64,
+    pub total_allocated: u64,
+    pub alloc_calls: u64,
+}
+
+/// All the local results that can be compared from a single profile folder
+#[derive(Debug, Default)]
+pub struct ProfileResults {
+    pub walltime: Vec<WalltimeEntry>,
+    pub memory: Vec<MemoryEntry>,
+}
+
+impl ProfileResults {
+    pub fn load(profile_folder: &Path) -> Result<Self> {
+        let results_dir = profile_folder.join("results");
+        if !results_dir.is_dir() {
+            bail!("No results directory found in profile folder: {results_dir:?}");
+        }
+
+        let results = Self {
+            walltime: load_walltime_entries(&results_dir)?,
+            memory: load_memory_entries(&results_dir)?,
+        };
+
+        if results.walltime.is_empty() && results.memory.is_empty() {
+            bail!("No walltime or memory results found in: {results_dir:?}");
+        }
+
+        Ok(results)
+    }
+}
+
+/// Load the walltime benchmarks of a profile folder, without requiring any to be present
+pub fn load_walltime_results(profile_folder: &Path) -> Result<Vec<WalltimeEntry>> {
+    let results_dir = profile_folder.join("results");
+    if !results_dir.is_dir() {
+        return Ok(vec![]);
+    }
+    load_walltime_entries(&results_dir)
+}
+
+fn load_walltime_entries(results_dir: &Path) -> Result<Vec<WalltimeEntry>> {
+    let mut entries: Vec<WalltimeEntry> = Vec::new();
+
+    for entry in std::fs::read_dir(results_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+
+        debug!("Parsing walltime results from {path:?}");
+        let file = std::fs::File::open(&path)
+            .with_context(|| format!("Failed to open walltime results file: {path:?}"))?;
+        let results: WalltimeResults = serde_json::from_reader(&file)
+            .with_context(|| format!("Failed to parse walltime results from: {path:?}"))?;
+
+        for benchmark in results.benchmarks {
+            if entries.iter().any(|e| e.uri == benchmark.metadata.uri) {
+                warn!(
+                    "Benchmark {} is present multiple times in {results_dir:?}, keeping the first occurrence",
+                    benchmark.metadata.uri
+                );
+                continue;
+            }
+            entries.push(WalltimeEntry {
+                name: benchmark.metadata.name,
+                uri: benchmark.metadata.uri,
+                stats: benchmark.stats,
+                resource_usage: benchmark.resource_usage,
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+fn load_memory_entries(results_dir: &Path) -> Result<Vec<MemoryEntry>> {
+    let timestamps_path = results_dir.join(ExecutionTimestamps::file_name(None));
+    if !timestamps_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let file = std::fs::File::open(&timestamps_path)
+        .with_context(|| format!("Failed to open {timestamps_path:?}"))?;
+    let timestamps = ExecutionTimestamps::decode_from_reader(file)
+        .with_context(|| format!("Failed to decode {timestamps_path:?}"))?;
+
+    let windows = benchmark_windows(&timestamps);
+    if windows.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut events_by_uri: HashMap<&str, Vec<MemtrackEvent>> = HashMap::new();
+    for entry in std::fs::read_dir(results_dir)? {
+        let path = entry?.path();
+        let is_memtrack_artifact = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().contains(MemtrackArtifact::name()));
+        if !is_memtrack_artifact {
+            continue;
+        }
+
+        debug!("Parsing memtrack events from {path:?}");
+        let file =
+            std::fs::File::open(&path).with_context(|| format!("Failed to open {path:?}"))?;
+        for event in MemtrackArtifact::decode_streamed(file)? {
+            if let Some(uri) = find_window_uri(&windows, event.timestamp) {
+                events_by_uri.entry(uri).or_default().push(event);
+            }
+        }
+    }
+
+    // Keep the execution order of the benchmarks
+    let mut entries = Vec::new();
+    for (_, uri) in &timestamps.uri_by_ts {
+        if entries.iter().any(|e: &MemoryEntry| &e.uri == uri) {
+            continue;
+        }
+        let mut events = events_by_uri.remove(uri.as_str()).unwrap_or_default();
+        events.sort_by_key(|e| e.timestamp);
+        entries.push(compute_memory_entry(uri.clone(), &events));
+    }
+
+    Ok(entries)
+}
+
+/// Pair up the benchmark markers and attribute each window to the URI that was reported right
+/// after it, since integrations report the executed benchmark once it has been stopped.
+fn benchmark_windows(timestamps: &ExecutionTimestamps) -> Vec<(u64, u64, &str)> {
+    let mut markers = timestamps.markers.clone();
+    markers.sort_by_key(|m| match m {
+        MarkerType::SampleStart(ts)
+        | MarkerType::SampleEnd(ts)
+        | MarkerType::BenchmarkStart(ts)
+        | MarkerType::BenchmarkEnd(ts) => *ts,
+    });
+
+    let mut uri_by_ts: Vec<_> = timestamps.uri_by_ts.iter().collect();
+    uri_by_ts.sort_by_key(|(ts, _)| *ts);
+
+    let mut windows = Vec::new();
+    let mut current_start = None;
+    for marker in markers {
+        match marker {
+            MarkerType::BenchmarkStart(ts) => current_start = Some(ts),
+            MarkerType::BenchmarkEnd(end) => {
+                let Some(start) = current_start.take() else {
+                    continue;
+                };
+                if let Some((_, uri)) = uri_by_ts.iter().copied().find(|(ts, _)| *ts >= end) {
+                    windows.push((start, end, uri.as_str()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    windows
+}
+
+fn find_window_uri<'a>(windows: &[(u64, u64, &'a str)], timestamp: u64) -> Option<&'a str> {
+    windows
+        .iter()
+        .find(|(start, end, _)| (*start..=*end).contains(&timestamp))
+        .map(|(_, _, uri)| *uri)
+}
+
+/// Replay the heap allocations of a benchmark. `events` must be sorted by timestamp.
+fn compute_memory_entry(uri: String, events: &[MemtrackEvent]) -> MemoryEntry {
+    let mut live_allocations: HashMap<u64, u64> = HashMap::new();
+    let mut current_memory: u64 = 0;
+    let mut entry = MemoryEntry {
+        uri,
+        ..Default::default()
+    };
+
+    for event in events {
+        let allocated_size = match event.kind {
+            MemtrackEventKind::Malloc { size }
+            | MemtrackEventKind::Calloc { size }
+            | MemtrackEventKind::AlignedAlloc { size } => size,
+            MemtrackEventKind::Realloc { old_addr, size } => {
+                if let Some(old_size) = old_addr.and_then(|addr| live_allocations.remove(&addr)) {
+                    current_memory = current_memory.saturating_sub(old_size);
+                }
+                size
+            }
+            MemtrackEventKind::Free => {
+                if let Some(size) = live_allocations.remove(&event.addr) {
+                    current_memory = current_memory.saturating_sub(size);
+                }
+                continue;
+            }
+            MemtrackEventKind::Mmap { .. }
+            | MemtrackEventKind::Munmap { .. }
+            | MemtrackEventKind::Brk { .. } => continue,
+        };
+
+        live_allocations.insert(event.addr, allocated_size);
+        current_memory += allocated_size;
+        entry.peak_memory = entry.peak_memory.max(current_memory);
+        entry.total_allocated += allocated_size;
+        entry.alloc_calls += 1;
+    }
+
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn event(timestamp: u64, addr: u64, kind: MemtrackEventKind) -> MemtrackEvent {
+        MemtrackEvent {
+            pid: 1,
+            tid: 1,
+            timestamp,
+            addr,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_compute_memory_entry() {
+        let events = vec![
+            event(1, 0x10, MemtrackEventKind::Malloc { size: 100 }),
+            event(2, 0x20, MemtrackEventKind::Calloc { size: 50 }),
+            event(3, 0x10, MemtrackEventKind::Free),
+            event(
+                4,
+                0x30,
+                MemtrackEventKind::Realloc {
+                    old_addr: Some(0x20),
+                    size: 80,
+                },
+            ),
+            event(5, 0x40, MemtrackEventKind::Mmap { size: 4096 }),
+        ];
+
+        let entry = compute_memory_entry("uri".into(), &events);
+        assert_eq!(
+            entry,
+            MemoryEntry {
+                uri: "uri".into(),
+                peak_memory: 150,
+                total_allocated: 230,
+                alloc_calls: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_benchmark_windows_are_attributed_to_next_uri() {
+        let timestamps = ExecutionTimestamps::new(
+            &[(25, "bench_a".into()), (45, "bench_b".into())],
+            &[
+                MarkerType::BenchmarkStart(10),
+                MarkerType::BenchmarkEnd(20),
+                MarkerType::BenchmarkStart(30),
+                MarkerType::BenchmarkEnd(40),
+            ],
+        );
+
+        let windows = benchmark_windows(&timestamps);
+        assert_eq!(windows, vec![(10, 20, "bench_a"), (30, 40, "bench_b")]);
+        assert_eq!(find_window_uri(&windows, 15), Some("bench_a"));
+        assert_eq!(find_window_uri(&windows, 25), None);
+        assert_eq!(find_window_uri(&windows, 40), Some("bench_b"));
+    }
+
+    #[test]
+    fn test_load_walltime_results() {
+        let profile_folder = TempDir::new().unwrap();
+        let results_dir = profile_folder.path().join("results");
+        std::fs::create_dir_all(&results_dir).unwrap();
+        std::fs::write(
+            results_dir.join("123.json"),
+            r#"{
+                "creator": {"name": "test", "version": "1.0.0", "pid": 123},
+                "instrument": {"type": "walltime"},
+                "benchmarks": [{
+                    "name": "bench",
+                    "uri": "test::bench",
+                    "config": {},
+                    "stats": {
+                        "min_ns": 1.0, "max_ns": 3.0, "mean_ns": 2.0, "stdev_ns": 1.0,
+                        "q1_ns": 1.5, "median_ns": 2.0, "q3_ns": 2.5,
+                        "rounds": 3, "total_time": 0.000000006,
+                        "iqr_outlier_rounds": 0, "stdev_outlier_rounds": 0,
+                        "iter_per_round": 1, "warmup_iters": 0
+                    }
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let results = ProfileResults::load(profile_folder.path()).unwrap();
+        assert_eq!(results.walltime.len(), 1);
+        assert_eq!(results.walltime[0].uri, "test::bench");
+        assert_eq!(results.walltime[0].stats.rounds, 3);
+        assert!(results.memory.is_empty());
+    }
+
+    #[test]
+    fn test_load_empty_profile_folder() {
+        let profile_folder = TempDir::new().unwrap();
+        assert!(ProfileResults::load(profile_folder.path()).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use runner_shared::walltime_results::BenchmarkStats;
+
+/// Two-sided Welch's t-test on the per-round times of two walltime benchmarks.
+///
+/// The per-round data is not persisted in the results, but the test only needs the mean, sample
+/// standard deviation and number of rounds, which are all part of the [`BenchmarkStats`].
+///
+/// Returns the p-value, or `None` when there is not enough data to run the test.
+pub fn welch_t_test(base: &BenchmarkStats, head: &BenchmarkStats) -> Option<f64> {
+    if base.rounds < 2 || head.rounds < 2 {
+        return None;
+    }
+
+    let base_n = base.rounds as f64;
+    let head_n = head.rounds as f64;
+    let base_var = base.stdev_ns.powi(2) / base_n;
+    let head_var = head.stdev_ns.powi(2) / head_n;
+    let std_err = (base_var + head_var).sqrt();
+    if std_err == 0.0 {
+        return None;
+    }
+
+    let t = (head.mean_ns - base.mean_ns) / std_err;
+    let degrees_of_freedom = (base_var + head_var).powi(2)
+        / (base_var.powi(2) / (base_n - 1.0) + head_var.powi(2) / (head_n - 1.0));
+
+    // The two-sided p-value of Student's t distribution is I_x(df/2, 1/2) with x = df/(df+t²)
+    let x = degrees_of_freedom / (degrees_of_freedom + t * t);
+    Some(regularized_incomplete_beta(
+        x,
+        degrees_of_freedom / 2.0,
+        0.5,
+    ))
+}
+
+/// Regularized incomplete beta function I_x(a, b), evaluated with Lentz's continued fraction.
+fn regularized_incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+
+    // The continued fraction converges quickly only for x < (a+1)/(a+b+2)
+    if x < (a + 1.0) / (a + b + 2.0) {
+        ln_front.exp() * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - ln_front.exp() * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 300;
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+
+        // Even step
+        let numerator = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 + numerator * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + numerator / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        result *= d * c;
+
+        // Odd step
+        let numerator = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 + numerator * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + numerator / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        result *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Lanczos approximation of ln(Γ(x)) for x > 0
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000000000190015;
+    for (i, coefficient) in COEFFICIENTS.iter().enumerate() {
+        series += coefficient / (x + 1.0 + i as f64);
+    }
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(mean_ns: f64, stdev_ns: f64, rounds: u64) -> BenchmarkStats {
+        BenchmarkStats {
+            min_ns: mean_ns,
+            max_ns: mean_ns,
+            mean_ns,
+            stdev_ns,
+            q1_ns: mean_ns,
+            median_ns: mean_ns,
+            q3_ns: mean_ns,
+            rounds,
+            total_time: mean_ns * rounds as f64 / 1_000_000_000.0,
+            iqr_outlier_rounds: 0,
+            stdev_outlier_rounds: 0,
+            iter_per_round: 1,
+            warmup_iters: 0,
+        }
+    }
+
+    #[test]
+    fn test_ln_gamma() {
+        assert!((ln_gamma(1.0)).abs() < 1e-8);
+        assert!((ln_gamma(5.0) - 24f64.ln()).abs() < 1e-8);
+        assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_identical_distributions_are_not_significant() {
+        let p_value = welch_t_test(&stats(100.0, 10.0, 50), &stats(100.0, 10.0, 50)).unwrap();
+        assert!((p_value - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reference_p_value() {
+        // Reference value obtained by numerically integrating the Student t density:
+        // t = 2.2136, df = 38, p = 0.03293
+        let p_value = welch_t_test(&stats(100.0, 10.0, 20), &stats(107.0, 10.0, 20)).unwrap();
+        assert!((p_value - 0.03293).abs() < 1e-4, "p_value = {p_value}");
+    }
+
+    #[test]
+    fn test_large_difference_is_significant() {
+        let p_value = welch_t_test(&stats(100.0, 1.0, 100), &stats(120.0, 1.0, 100)).unwrap();
+        assert!(p_value < 1e-8);
+    }
+
+    #[test]
+    fn test_not_enough_data() {
+        assert_eq!(
+            welch_t_test(&stats(100.0, 0.0, 1), &stats(120.0, 1.0, 10)),
+            None
+        );
+        assert_eq!(
+            welch_t_test(&stats(100.0, 0.0, 10), &stats(120.0, 0.0, 10)),
+            None
+        );
+    }
+}
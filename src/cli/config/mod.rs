@@ -0,0 +1,39 @@
+use crate::prelude::*;
+use clap::{ArgMatches, Args, Subcommand};
+use std::path::Path;
+
+mod show;
+mod validate;
+
+#[derive(Debug, Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    command: ConfigCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigCommands {
+    /// Check the project configuration file against the configuration schema
+    Validate(validate::ValidateArgs),
+    /// Show the project configuration file used from the current directory
+    Show(show::ShowArgs),
+}
+
+pub fn run(
+    args: ConfigArgs,
+    matches: &ArgMatches,
+    config_path: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        ConfigCommands::Validate(args) => validate::run(args, config_path)?,
+        ConfigCommands::Show(args) => {
+            let matches = matches
+                .subcommand_matches("config")
+                .and_then(|matches| matches.subcommand_matches("show"))
+                .context("Missing arguments of the config show command")?;
+            show::run(args, matches, config_path, profile)?
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,468 @@
+use crate::cli::ExecAndRunSharedArgs;
+use crate::cli::exec::ExecArgs;
+use crate::executor::Config;
+use crate::prelude::*;
+use crate::project_config::manifest::describe_source;
+use crate::project_config::merger::ConfigMerger;
+use crate::project_config::{ProjectConfig, WalltimeOptions};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, ValueEnum};
+use console::style;
+use exec_harness::walltime::WalltimeExecutionArgs;
+use std::fmt::Display;
+use std::path::Path;
+
+#[derive(Debug, Args)]
+pub struct ShowArgs {
+    /// Show the configuration resolved from the command line arguments, the environment and the
+    /// configuration file, along with the origin of each value.
+    /// The arguments of `codspeed exec` can be passed to see how they are merged.
+    #[arg(long)]
+    pub resolved: bool,
+
+    #[command(flatten)]
+    pub shared: ExecAndRunSharedArgs,
+
+    #[command(flatten)]
+    pub walltime_args: WalltimeExecutionArgs,
+}
+
+/// Where a resolved value comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueOrigin {
+    CommandLine,
+    Environment,
+    ShellSession,
+    ConfigFile,
+    Target,
+    Default,
+}
+
+impl Display for ValueOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueOrigin::CommandLine => write!(f, "command line"),
+            ValueOrigin::Environment => write!(f, "environment"),
+            ValueOrigin::ShellSession => write!(f, "shell session"),
+            ValueOrigin::ConfigFile => write!(f, "config file"),
+            ValueOrigin::Target => write!(f, "target"),
+            ValueOrigin::Default => write!(f, "default"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ResolvedValue {
+    name: String,
+    value: Option<String>,
+    origin: ValueOrigin,
+}
+
+#[derive(Debug)]
+struct ResolvedSection {
+    title: String,
+    values: Vec<ResolvedValue>,
+}
+
+pub fn run(
+    args: ShowArgs,
+    matches: &ArgMatches,
+    config_path: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let config_path = ProjectConfig::discover_path(config_path, &current_dir)?;
+    let project_config = config_path
+        .as_deref()
+        .map(ProjectConfig::load_from_path)
+        .transpose()?;
+
+    if !args.resolved {
+        let (Some(config_path), Some(project_config)) = (config_path, project_config) else {
+            bail!("No configuration file found in the current directory or its parents");
+        };
+        info!("# {}", describe_source(&config_path));
+        info!("{}", serde_yaml::to_string(&project_config)?.trim_end());
+        return Ok(());
+    }
+
+    match &config_path {
+        Some(config_path) => {
+            info!("Configuration file: {}", describe_source(config_path));
+            if let Some(config_dir) = config_path.parent().filter(|dir| *dir != current_dir) {
+                info!("Commands run from: {}", config_dir.display());
+            }
+        }
+        None => info!("No configuration file found"),
+    }
+
+    let project_config = match (project_config, profile) {
+        (Some(project_config), Some(profile)) => {
+            info!("Profile: {profile}");
+            Some(project_config.with_profile(profile)?)
+        }
+        (None, Some(profile)) => {
+            bail!("The profile `{profile}` is selected but no codspeed.yaml was found")
+        }
+        (project_config, None) => project_config,
+    };
+
+    for section in resolve(args, matches, project_config.as_ref())? {
+        info!("\n{}", style(&section.title).bold());
+        for resolved in &section.values {
+            info!(
+                "  {}: {} {}",
+                resolved.name,
+                resolved.value.as_deref().unwrap_or("not set"),
+                style(format!("({})", resolved.origin)).dim()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn resolve(
+    args: ShowArgs,
+    matches: &ArgMatches,
+    project_config: Option<&ProjectConfig>,
+) -> Result<Vec<ResolvedSection>> {
+    let project_options = project_config.and_then(|c| c.options.as_ref());
+    let default_walltime = project_options.and_then(|o| o.walltime.as_ref());
+
+    let exec_args = ExecArgs {
+        shared: args.shared,
+        walltime_args: args.walltime_args,
+        filter_args: Default::default(),
+        scheduling_args: Default::default(),
+        assertions: Default::default(),
+        stdio: Default::default(),
+        name: None,
+        command: vec![],
+    }
+    .merge_with_project_config(project_config);
+    let merged_walltime = walltime_args_to_options(&exec_args.walltime_args)?;
+    let config = Config::try_from(exec_args)?;
+
+    let origin = |id: &str, from_config: bool| {
+        arg_origin(matches, id).unwrap_or(if from_config {
+            ValueOrigin::ConfigFile
+        } else {
+            ValueOrigin::Default
+        })
+    };
+
+    let mut sections = vec![ResolvedSection {
+        title: "Run configuration".into(),
+        values: vec![
+            ResolvedValue {
+                name: "mode".into(),
+                value: Some(value_enum_name(&config.mode)),
+                origin: arg_origin(matches, "mode").unwrap_or(
+                    if project_options.is_some_and(|o| o.mode.is_some()) {
+                        ValueOrigin::ConfigFile
+                    } else {
+                        ValueOrigin::ShellSession
+                    },
+                ),
+            },
+            ResolvedValue {
+                name: "working-directory".into(),
+                value: config.working_directory.clone(),
+                origin: origin(
+                    "working_directory",
+                    project_options.is_some_and(|o| o.working_directory.is_some()),
+                ),
+            },
+            ResolvedValue {
+                name: "upload-url".into(),
+                value: Some(config.upload_url.to_string()),
+                origin: origin("upload_url", false),
+            },
+            ResolvedValue {
+                name: "token".into(),
+                value: config.token.as_ref().map(|_| "********".into()),
+                origin: origin("token", false),
+            },
+            ResolvedValue {
+                name: "repository".into(),
+                value: config
+                    .repository_override
+                    .as_ref()
+                    .map(|r| format!("{}/{}", r.owner, r.repository)),
+                origin: origin("repository", false),
+            },
+            ResolvedValue {
+                name: "enable-perf".into(),
+                value: Some(config.enable_perf.to_string()),
+                origin: origin(
+                    "enable_perf",
+                    project_options.is_some_and(|o| o.enable_perf.is_some()),
+                ),
+            },
+            ResolvedValue {
+                name: "perf-unwinding-mode".into(),
+                value: config.perf_unwinding_mode.as_ref().map(value_enum_name),
+                origin: origin(
+                    "perf_unwinding_mode",
+                    project_options.is_some_and(|o| o.perf_unwinding_mode.is_some()),
+                ),
+            },
+            ResolvedValue {
+                name: "allow-empty".into(),
+                value: Some(config.allow_empty.to_string()),
+                origin: origin(
+                    "allow_empty",
+                    project_options.is_some_and(|o| o.allow_empty.is_some()),
+                ),
+            },
+            ResolvedValue {
+                name: "profile-folder".into(),
+                value: config
+                    .profile_folder
+                    .as_ref()
+                    .map(|p| p.display().to_string()),
+                origin: origin("profile_folder", false),
+            },
+        ],
+    }];
+
+    let targets = project_config
+        .and_then(|c| c.benchmarks.as_ref())
+        .filter(|targets| !targets.is_empty());
+    match targets {
+        // Targets only merge their options with the root options of the config file
+        Some(targets) => {
+            for target in targets {
+                let target_walltime = target.options.as_ref().and_then(|o| o.walltime.as_ref());
+                let target_values = walltime_values(target_walltime)?;
+                let walltime =
+                    ConfigMerger::merge_walltime_overrides(default_walltime, target_walltime);
+                let values =
+                    walltime_values(walltime.as_ref())?
+                        .into_iter()
+                        .map(|(name, value)| {
+                            let from_target = target_values.iter().any(|(n, _)| *n == name);
+                            ResolvedValue {
+                                name,
+                                value: Some(value),
+                                origin: if from_target {
+                                    ValueOrigin::Target
+                                } else {
+                                    ValueOrigin::ConfigFile
+                                },
+                            }
+                        });
+
+                // Walltime options do not apply to `run` targets, their harness runs them
+                let (command_name, values) = match target.exec {
+                    Some(_) => ("exec", Some(values)),
+                    None => ("run", None),
+                };
+                sections.push(ResolvedSection {
+                    title: format!("Target {}", target.display_name()),
+                    values: std::iter::once(ResolvedValue {
+                        name: command_name.into(),
+                        value: Some(target.command().to_owned()),
+                        origin: ValueOrigin::ConfigFile,
+                    })
+                    .chain(values.into_iter().flatten())
+                    .collect(),
+                });
+            }
+        }
+        None => {
+            let values = walltime_values(Some(&merged_walltime))?
+                .into_iter()
+                .map(|(name, value)| ResolvedValue {
+                    origin: origin(&name.replace('-', "_"), true),
+                    name,
+                    value: Some(value),
+                })
+                .collect();
+            sections.push(ResolvedSection {
+                title: "Walltime options".into(),
+                values,
+            });
+        }
+    }
+
+    Ok(sections)
+}
+
+fn arg_origin(matches: &ArgMatches, id: &str) -> Option<ValueOrigin> {
+    match matches.value_source(id)? {
+        ValueSource::CommandLine => Some(ValueOrigin::CommandLine),
+        ValueSource::EnvVariable => Some(ValueOrigin::Environment),
+        _ => None,
+    }
+}
+
+fn value_enum_name(value: &impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+fn walltime_args_to_options(args: &WalltimeExecutionArgs) -> Result<WalltimeOptions> {
+    Ok(WalltimeOptions {
+        warmup_time: args.warmup_time.clone(),
+        max_time: args.max_time.clone(),
+        min_time: args.min_time.clone(),
+        max_rounds: args.max_rounds,
+        min_rounds: args.min_rounds,
+        iters_per_round: args
+            .iters_per_round
+            .as_deref()
+            .map(str::parse)
+            .transpose()?,
+        target_precision: args.target_precision.clone(),
+        cpu_affinity: args.process_settings.cpu_affinity.clone(),
+        nice: args.process_settings.nice,
+        sched_fifo: args.process_settings.sched_fifo,
+        disable_aslr: args.process_settings.disable_aslr.then_some(true),
+    })
+}
+
+/// Walltime options set in `options`, with their names and values in the configuration file
+fn walltime_values(options: Option<&WalltimeOptions>) -> Result<Vec<(String, String)>> {
+    let Some(options) = options else {
+        return Ok(vec![]);
+    };
+    let serde_json::Value::Object(values) = serde_json::to_value(options)? else {
+        bail!("Walltime options must serialize to a map");
+    };
+
+    Ok(values
+        .into_iter()
+        .map(|(name, value)| match value {
+            serde_json::Value::String(value) => (name, value),
+            value => (name, value.to_string()),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Command, FromArgMatches};
+
+    fn resolve_from(cli_args: &[&str], config: &str) -> Vec<ResolvedSection> {
+        let matches = ShowArgs::augment_args(Command::new("show"))
+            .try_get_matches_from(std::iter::once("show").chain(cli_args.iter().copied()))
+            .unwrap();
+        let args = ShowArgs::from_arg_matches(&matches).unwrap();
+        let project_config: ProjectConfig = serde_yaml::from_str(config).unwrap();
+        resolve(args, &matches, Some(&project_config)).unwrap()
+    }
+
+    fn find<'a>(section: &'a ResolvedSection, name: &str) -> &'a ResolvedValue {
+        section.values.iter().find(|v| v.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_exec_precedence() {
+        let sections = resolve_from(
+            &["--mode", "walltime", "--max-time", "5s"],
+            r#"
+options:
+  working-directory: ./bench
+  warmup-time: 2s
+  max-time: 10s
+"#,
+        );
+
+        let run = &sections[0];
+        assert_eq!(find(run, "mode").value.as_deref(), Some("walltime"));
+        assert_eq!(find(run, "mode").origin, ValueOrigin::CommandLine);
+        assert_eq!(
+            find(run, "working-directory").origin,
+            ValueOrigin::ConfigFile
+        );
+
+        let walltime = &sections[1];
+        assert_eq!(
+            find(walltime, "max-time"),
+            &ResolvedValue {
+                name: "max-time".into(),
+                value: Some("5s".into()),
+                origin: ValueOrigin::CommandLine,
+            }
+        );
+        assert_eq!(
+            find(walltime, "warmup-time").origin,
+            ValueOrigin::ConfigFile
+        );
+        assert!(walltime.values.iter().all(|v| v.name != "min-rounds"));
+    }
+
+    #[test]
+    fn test_resolve_run_options_from_config() {
+        let sections = resolve_from(
+            &["--perf-unwinding-mode", "fp"],
+            r#"
+options:
+  mode: walltime
+  enable-perf: false
+  perf-unwinding-mode: dwarf
+"#,
+        );
+
+        let run = &sections[0];
+        assert_eq!(find(run, "mode").value.as_deref(), Some("walltime"));
+        assert_eq!(find(run, "mode").origin, ValueOrigin::ConfigFile);
+        assert_eq!(find(run, "enable-perf").value.as_deref(), Some("false"));
+        assert_eq!(find(run, "enable-perf").origin, ValueOrigin::ConfigFile);
+        assert_eq!(
+            find(run, "perf-unwinding-mode"),
+            &ResolvedValue {
+                name: "perf-unwinding-mode".into(),
+                value: Some("fp".into()),
+                origin: ValueOrigin::CommandLine,
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_targets() {
+        let sections = resolve_from(
+            &["--mode", "walltime"],
+            r#"
+options:
+  warmup-time: 2s
+benchmarks:
+  - name: fast
+    exec: ./fast
+    options:
+      warmup-time: 500ms
+  - exec: ./slow
+  - run: cargo codspeed run
+"#,
+        );
+
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[1].title, "Target fast");
+        assert_eq!(
+            find(&sections[1], "warmup-time").value.as_deref(),
+            Some("500ms")
+        );
+        assert_eq!(
+            find(&sections[1], "warmup-time").origin,
+            ValueOrigin::Target
+        );
+        assert_eq!(sections[2].title, "Target ./slow");
+        assert_eq!(
+            find(&sections[2], "warmup-time").origin,
+            ValueOrigin::ConfigFile
+        );
+        assert_eq!(
+            sections[3].values,
+            vec![ResolvedValue {
+                name: "run".into(),
+                value: Some("cargo codspeed run".into()),
+                origin: ValueOrigin::ConfigFile,
+            }]
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::prelude::*;
+use crate::project_config::ProjectConfig;
+use crate::project_config::manifest::{describe_source, is_manifest, read_manifest_table};
+use crate::project_config::schema::{IssueSeverity, check_schema, check_schema_value};
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    /// Path of the configuration file to validate, defaults to the codspeed.yaml found from the
+    /// current directory
+    pub path: Option<PathBuf>,
+}
+
+pub fn run(args: ValidateArgs, config_path: Option<&Path>) -> Result<()> {
+    let path = ProjectConfig::discover_path(
+        args.path.as_deref().or(config_path),
+        &std::env::current_dir()?,
+    )?
+    .context("No configuration file found in the current directory or its parents")?;
+
+    let source = describe_source(&path);
+    let issues = if is_manifest(&path) {
+        let (_, table) = read_manifest_table(&path)?
+            .with_context(|| format!("No CodSpeed project config found in {}", path.display()))?;
+        let value =
+            serde_json::to_value(table).with_context(|| format!("Invalid config in {source}"))?;
+        check_schema_value(&value)?
+    } else {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file at {}", path.display()))?;
+        check_schema(&content).with_context(|| format!("Invalid YAML in {}", path.display()))?
+    };
+
+    for issue in &issues {
+        let location = match issue.line {
+            Some(line) => format!("{}:{line}", path.display()),
+            None => source.clone(),
+        };
+        match issue.severity {
+            IssueSeverity::Error => {
+                error!("{location}: {}: {}", issue.path_display(), issue.message)
+            }
+            IssueSeverity::Warning => {
+                warn!("{location}: {}: {}", issue.path_display(), issue.message)
+            }
+        }
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == IssueSeverity::Error)
+        .count();
+    if errors > 0 {
+        bail!("{source} is invalid, {errors} error(s) found");
+    }
+
+    // The schema does not capture the constraints between options, nor the extended and
+    // included files
+    ProjectConfig::load_from_path(&path).with_context(|| format!("{source} is invalid"))?;
+
+    info!("{source} is valid");
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use crate::cli::run::MessageFormat;
+use crate::executor::{Diagnostic, DiagnosticStatus, get_all_executors};
+use crate::prelude::*;
+use crate::system::{SystemInfo, check_system, is_officially_supported};
+use clap::Args;
+use console::style;
+use serde::Serialize;
+
+#[derive(Debug, Args)]
+pub struct DoctorArgs {
+    /// Output format of the report, `json` outputs a machine readable report
+    #[arg(long)]
+    pub message_format: Option<MessageFormat>,
+}
+
+#[derive(Debug, Serialize)]
+struct DoctorReport {
+    system_info: Option<SystemInfo>,
+    sections: Vec<DoctorSection>,
+}
+
+#[derive(Debug, Serialize)]
+struct DoctorSection {
+    name: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl DoctorReport {
+    fn count(&self, status: DiagnosticStatus) -> usize {
+        self.sections
+            .iter()
+            .flat_map(|section| &section.diagnostics)
+            .filter(|diagnostic| diagnostic.status == status)
+            .count()
+    }
+}
+
+pub fn run(args: DoctorArgs) -> Result<()> {
+    let report = build_report();
+
+    if args.message_format == Some(MessageFormat::Json) {
+        log_json!(serde_json::to_string(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    let failures = report.count(DiagnosticStatus::Fail);
+    if failures > 0 {
+        bail!("{failures} check(s) failed, see the report above");
+    }
+
+    Ok(())
+}
+
+fn build_report() -> DoctorReport {
+    let system_info = match SystemInfo::new() {
+        Ok(system_info) => system_info,
+        Err(e) => {
+            return DoctorReport {
+                system_info: None,
+                sections: vec![DoctorSection {
+                    name: "system".into(),
+                    diagnostics: vec![Diagnostic::fail("system info", e)],
+                }],
+            };
+        }
+    };
+
+    let system_name = format!(
+        "{} {} ({})",
+        system_info.os, system_info.os_version, system_info.arch
+    );
+    let system_diagnostic = if is_officially_supported(&system_info) {
+        Diagnostic::pass("supported system", system_name)
+    } else if check_system(&system_info).is_ok() {
+        Diagnostic::warn(
+            "supported system",
+            format!("{system_name} is supported on a best effort basis"),
+        )
+    } else {
+        Diagnostic::fail(
+            "supported system",
+            format!("{system_name} is not supported"),
+        )
+    };
+
+    let mut sections = vec![DoctorSection {
+        name: "system".into(),
+        diagnostics: vec![system_diagnostic],
+    }];
+    sections.extend(
+        get_all_executors()
+            .into_iter()
+            .map(|executor| DoctorSection {
+                name: executor.name().to_string(),
+                diagnostics: executor.diagnose(&system_info),
+            }),
+    );
+
+    DoctorReport {
+        system_info: Some(system_info),
+        sections,
+    }
+}
+
+fn print_report(report: &DoctorReport) {
+    for section in &report.sections {
+        info!("{}", style(&section.name).bold());
+        for diagnostic in &section.diagnostics {
+            let icon = match diagnostic.status {
+                DiagnosticStatus::Pass => style("✔").green(),
+                DiagnosticStatus::Warn => style("!").yellow(),
+                DiagnosticStatus::Fail => style("✘").red(),
+            };
+            info!(
+                "  {icon} {}: {}",
+                style(&diagnostic.name).bold(),
+                diagnostic.message
+            );
+        }
+    }
+
+    info!(
+        "\n{} passed, {} warning(s), {} failed",
+        report.count(DiagnosticStatus::Pass),
+        report.count(DiagnosticStatus::Warn),
+        report.count(DiagnosticStatus::Fail)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_count() {
+        let report = DoctorReport {
+            system_info: None,
+            sections: vec![
+                DoctorSection {
+                    name: "system".into(),
+                    diagnostics: vec![Diagnostic::pass("supported system", "ubuntu 24.04")],
+                },
+                DoctorSection {
+                    name: "walltime".into(),
+                    diagnostics: vec![
+                        Diagnostic::warn("perf", "not installed"),
+                        Diagnostic::fail("systemd-run", "not found in PATH"),
+                        Diagnostic::pass("sudo", "running as root"),
+                    ],
+                },
+            ],
+        };
+
+        assert_eq!(report.count(DiagnosticStatus::Pass), 2);
+        assert_eq!(report.count(DiagnosticStatus::Warn), 1);
+        assert_eq!(report.count(DiagnosticStatus::Fail), 1);
+        assert_eq!(
+            serde_json::to_value(&report.sections[1].diagnostics[1]).unwrap(),
+            serde_json::json!({
+                "name": "systemd-run",
+                "status": "fail",
+                "message": "not found in PATH"
+            })
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::prelude::*;
+use crate::project_config::Target;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::collections::BTreeMap;
+
+lazy_static! {
+    /// Placeholder of a matrix parameter, e.g. `{{size}}`
+    static ref PLACEHOLDER: Regex = Regex::new(r"\{\{\s*([A-Za-z0-9_-]+)\s*\}\}").unwrap();
+}
+
+/// Values of the matrix parameters for one expansion of a target, by parameter name
+pub type MatrixParams = BTreeMap<String, String>;
+
+/// Every combination of the matrix parameters of a target, in a deterministic order.
+/// A target without matrix has a single expansion without parameters.
+pub fn expand(target: &Target) -> Result<Vec<MatrixParams>> {
+    let mut combinations = vec![MatrixParams::new()];
+    let Some(matrix) = &target.matrix else {
+        return Ok(combinations);
+    };
+
+    for (name, values) in matrix {
+        if values.is_empty() {
+            bail!(
+                "Matrix parameter `{name}` of target {} has no values",
+                target.display_name()
+            );
+        }
+
+        combinations = combinations
+            .into_iter()
+            .cartesian_product(values)
+            .map(|(mut params, value)| {
+                params.insert(name.clone(), value.to_string());
+                params
+            })
+            .collect();
+    }
+
+    Ok(combinations)
+}
+
+/// Replace the `{{name}}` placeholders of a template with the values of the matrix parameters
+pub fn substitute(template: &str, params: &MatrixParams) -> Result<String> {
+    // Targets without matrix are left untouched, they may legitimately contain braces
+    if params.is_empty() {
+        return Ok(template.to_string());
+    }
+
+    let mut unknown = None;
+    let substituted = PLACEHOLDER.replace_all(template, |captures: &Captures| {
+        match params.get(&captures[1]) {
+            Some(value) => value.clone(),
+            None => {
+                unknown.get_or_insert_with(|| captures[1].to_string());
+                captures[0].to_string()
+            }
+        }
+    });
+
+    if let Some(name) = unknown {
+        bail!(
+            "Unknown matrix parameter `{name}` in `{template}`, available parameters: {}",
+            params.keys().join(", ")
+        );
+    }
+
+    Ok(substituted.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(yaml: &str) -> Target {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn params(values: &[(&str, &str)]) -> MatrixParams {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_without_matrix() {
+        let target = target("exec: ./bench");
+        assert_eq!(expand(&target).unwrap(), vec![MatrixParams::new()]);
+    }
+
+    #[test]
+    fn test_expand_matrix() {
+        let target = target(
+            r#"
+exec: ./bench --size {{size}} --threads {{threads}}
+matrix:
+  threads: [1, 8]
+  size: [1k, 1M]
+"#,
+        );
+
+        assert_eq!(
+            expand(&target).unwrap(),
+            vec![
+                params(&[("size", "1k"), ("threads", "1")]),
+                params(&[("size", "1k"), ("threads", "8")]),
+                params(&[("size", "1M"), ("threads", "1")]),
+                params(&[("size", "1M"), ("threads", "8")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_empty_parameter() {
+        let target = target("exec: ./bench\nmatrix:\n  size: []\n");
+        assert!(expand(&target).is_err());
+    }
+
+    #[test]
+    fn test_substitute() {
+        let params = params(&[("size", "1k"), ("threads", "8")]);
+        assert_eq!(
+            substitute("./bench --size {{size}} -j{{ threads }}", &params).unwrap(),
+            "./bench --size 1k -j8"
+        );
+        assert!(substitute("./bench {{sizes}}", &params).is_err());
+
+        // Templates of targets without matrix are not substituted
+        assert_eq!(
+            substitute("echo {{size}}", &MatrixParams::new()).unwrap(),
+            "echo {{size}}"
+        );
+    }
+}
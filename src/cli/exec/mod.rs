@@ -8,8 +8,13 @@ use crate::project_config::ProjectConfig;
 use crate::project_config::merger::ConfigMerger;
 use crate::upload::UploadResult;
 use clap::Args;
+use exec_harness::assertions::BenchmarkAssertions;
+use exec_harness::filter::BenchmarkFilterArgs;
+use exec_harness::stdio::BenchmarkStdio;
+use exec_harness::walltime::SchedulingArgs;
 use std::path::Path;
 
+pub mod matrix;
 pub mod multi_targets;
 mod poll_results;
 
@@ -19,17 +24,26 @@ pub const DEFAULT_REPOSITORY_NAME: &str = "local-runs";
 const EXEC_HARNESS_COMMAND: &str = "exec-harness";
 const EXEC_HARNESS_VERSION: &str = "1.1.0";
 
-/// Wraps a command with exec-harness and the given walltime arguments.
+/// Wraps a command with exec-harness and the given walltime, benchmark selection, scheduling,
+/// assertion and stdio arguments.
 ///
 /// This produces a shell command string like:
 /// `exec-harness --warmup-time 1s --max-rounds 10 sleep 0.1`
 pub fn wrap_with_exec_harness(
     walltime_args: &exec_harness::walltime::WalltimeExecutionArgs,
+    filter_args: &BenchmarkFilterArgs,
+    scheduling_args: &SchedulingArgs,
+    assertions: &BenchmarkAssertions,
+    stdio: &BenchmarkStdio,
     command: &[String],
 ) -> String {
     shell_words::join(
         std::iter::once(EXEC_HARNESS_COMMAND)
             .chain(walltime_args.to_cli_args().iter().map(|s| s.as_str()))
+            .chain(filter_args.to_cli_args().iter().map(|s| s.as_str()))
+            .chain(scheduling_args.to_cli_args().iter().map(|s| s.as_str()))
+            .chain(assertions.to_cli_args().iter().map(|s| s.as_str()))
+            .chain(stdio.to_cli_args().iter().map(|s| s.as_str()))
             .chain(command.iter().map(|s| s.as_str())),
     )
 }
@@ -42,6 +56,22 @@ pub struct ExecArgs {
     #[command(flatten)]
     pub walltime_args: exec_harness::walltime::WalltimeExecutionArgs,
 
+    /// Only applies when the command is `-`, reading a JSON list of benchmarks from stdin
+    #[command(flatten)]
+    pub filter_args: BenchmarkFilterArgs,
+
+    /// Only applies when the command is `-`, reading a JSON list of benchmarks from stdin
+    #[command(flatten)]
+    pub scheduling_args: SchedulingArgs,
+
+    /// Only applies to a single command, the benchmarks read from stdin define their own
+    #[command(flatten)]
+    pub assertions: BenchmarkAssertions,
+
+    /// Only applies to a single command, the benchmarks read from stdin define their own
+    #[command(flatten)]
+    pub stdio: BenchmarkStdio,
+
     /// Optional benchmark name (defaults to command filename)
     #[arg(long)]
     pub name: Option<String>,
@@ -67,6 +97,10 @@ impl ExecArgs {
                     .as_ref()
                     .and_then(|o| o.walltime.as_ref()),
             );
+            self.scheduling_args = ConfigMerger::merge_scheduling_args(
+                &self.scheduling_args,
+                project_config.options.as_ref(),
+            );
         }
         self
     }
@@ -82,7 +116,7 @@ pub async fn run(
     let merged_args = args.merge_with_project_config(project_config);
     let config = crate::executor::Config::try_from(merged_args)?;
 
-    execute_with_harness(config, api_client, codspeed_config, setup_cache_dir).await
+    execute_with_harness(config, api_client, codspeed_config, setup_cache_dir, false).await
 }
 
 /// Core execution logic for exec-harness based runs.
@@ -95,6 +129,7 @@ pub async fn execute_with_harness(
     api_client: &CodSpeedAPIClient,
     codspeed_config: &CodSpeedConfig,
     setup_cache_dir: Option<&Path>,
+    output_json: bool,
 ) -> Result<()> {
     let mut execution_context =
         executor::ExecutionContext::new(config, codspeed_config, api_client).await?;
@@ -120,8 +155,9 @@ pub async fn execute_with_harness(
     )
     .await?;
 
+    let profile_folder = execution_context.profile_folder.clone();
     let poll_results_fn = async |upload_result: &UploadResult| {
-        poll_results::poll_results(api_client, upload_result).await
+        poll_results::poll_results(api_client, upload_result, &profile_folder, output_json).await
     };
 
     executor::execute_benchmarks(
@@ -1,38 +1,197 @@
 use super::EXEC_HARNESS_COMMAND;
+use super::matrix;
 use crate::prelude::*;
+use crate::project_config::OutputTarget;
 use crate::project_config::Target;
 use crate::project_config::WalltimeOptions;
+use crate::runner_mode::RunnerMode;
 use exec_harness::BenchmarkCommand;
+use exec_harness::assertions::BenchmarkAssertions;
+use exec_harness::filter::{BenchmarkFilter, BenchmarkFilterArgs};
+use exec_harness::hooks::BenchmarkHooks;
+use exec_harness::stdio::BenchmarkStdio;
+use exec_harness::walltime::SchedulingArgs;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Keep the targets selected by the `--bench` and `--exclude` patterns
+pub fn filter_targets<'a>(
+    targets: &'a [Target],
+    filter_args: &BenchmarkFilterArgs,
+) -> Result<Vec<&'a Target>> {
+    let filter = BenchmarkFilter::try_from(filter_args)?;
+    let selected = targets
+        .iter()
+        .filter(|target| filter.matches(target.display_name()))
+        .collect::<Vec<_>>();
+
+    if selected.is_empty() {
+        bail!(
+            "No target of codspeed.yaml matches the --bench and --exclude patterns, available targets: {}",
+            targets.iter().map(Target::display_name).join(", ")
+        );
+    }
+
+    Ok(selected)
+}
+
+/// Group the targets by the modes they run in, in the order the modes first appear.
+///
+/// The modes of a target are its own `modes`, then the root `modes`, then the mode of the run
+/// given by `default_mode`, which is only resolved when a target needs it. A mode given on the
+/// command line or in the environment, `explicit_mode`, only keeps the targets running in that
+/// mode.
+pub fn group_targets_by_mode<'a>(
+    targets: &[&'a Target],
+    root_modes: Option<&[RunnerMode]>,
+    explicit_mode: Option<RunnerMode>,
+    default_mode: impl FnOnce() -> Result<RunnerMode>,
+) -> Result<Vec<(RunnerMode, Vec<&'a Target>)>> {
+    let mut default_mode = Some(default_mode);
+    let mut resolved_default_mode = None;
+    let mut groups: Vec<(RunnerMode, Vec<&'a Target>)> = vec![];
+
+    for target in targets {
+        let modes = match target.modes.as_deref().or(root_modes) {
+            Some([]) => bail!(
+                "Target {} has an empty list of modes",
+                target.display_name()
+            ),
+            Some(modes) => modes.to_vec(),
+            None => {
+                if let Some(default_mode) = default_mode.take() {
+                    resolved_default_mode = Some(default_mode()?);
+                }
+                resolved_default_mode.clone().into_iter().collect()
+            }
+        };
+
+        let modes = match &explicit_mode {
+            Some(mode) if modes.contains(mode) => vec![mode.clone()],
+            Some(mode) => {
+                info!(
+                    "Skipping target {}, it does not run in the {mode:?} mode",
+                    target.display_name()
+                );
+                continue;
+            }
+            None => modes,
+        };
+
+        for mode in modes.into_iter().unique() {
+            match groups
+                .iter_mut()
+                .find(|(group_mode, _)| *group_mode == mode)
+            {
+                Some((_, group)) => group.push(target),
+                None => groups.push((mode, vec![target])),
+            }
+        }
+    }
+
+    if let (Some(mode), true) = (&explicit_mode, groups.is_empty()) {
+        bail!("No target of codspeed.yaml runs in the {mode:?} mode");
+    }
+
+    Ok(groups)
+}
 
 /// Convert targets from project config to exec-harness JSON input format
 pub fn targets_to_exec_harness_json(
-    targets: &[Target],
+    targets: &[&Target],
     default_walltime: Option<&WalltimeOptions>,
 ) -> Result<String> {
+    // Target working directories are relative to the config file, which is the current directory
+    let config_dir = std::env::current_dir().context("Failed to get current directory")?;
     let inputs: Vec<BenchmarkCommand> = targets
         .iter()
         .map(|target| {
-            // Parse the exec string into command parts
-            let command = shell_words::split(&target.exec)
-                .with_context(|| format!("Failed to parse command: {}", target.exec))?;
-
             // Merge target-specific walltime options with defaults
             let target_walltime = target.options.as_ref().and_then(|o| o.walltime.as_ref());
             let walltime_args = merge_walltime_options(default_walltime, target_walltime);
 
-            Ok(BenchmarkCommand {
-                command,
-                name: target.name.clone(),
-                walltime_args,
-            })
+            // Each combination of the matrix parameters is a separate benchmark
+            matrix::expand(target)?
+                .into_iter()
+                .map(|params| {
+                    let substitute = |template: &str| matrix::substitute(template, &params);
+                    let substitute_hook =
+                        |hook: &Option<String>| hook.as_deref().map(substitute).transpose();
+
+                    // Parse the exec string into command parts
+                    let exec = substitute(target.command())?;
+                    let command = shell_words::split(&exec)
+                        .with_context(|| format!("Failed to parse command: {exec}"))?;
+
+                    let env: BTreeMap<_, _> = target
+                        .env
+                        .iter()
+                        .flatten()
+                        .map(|(key, value)| Ok((key.clone(), substitute(value)?)))
+                        .collect::<Result<_>>()?;
+
+                    // Expansions are named after the target, the parameters are appended by
+                    // exec-harness
+                    let name = if params.is_empty() {
+                        target.name.clone()
+                    } else {
+                        Some(target.display_name().to_string())
+                    };
+
+                    let hooks = BenchmarkHooks {
+                        setup: substitute_hook(&target.setup)?,
+                        teardown: substitute_hook(&target.teardown)?,
+                        before_each: substitute_hook(&target.before_each)?,
+                        after_each: substitute_hook(&target.after_each)?,
+                    };
+
+                    Ok(BenchmarkCommand {
+                        command,
+                        name,
+                        walltime_args: walltime_args.clone(),
+                        env,
+                        working_directory: target
+                            .working_directory
+                            .as_ref()
+                            .map(|dir| config_dir.join(dir)),
+                        timeout: target.timeout.clone(),
+                        hooks,
+                        assertions: BenchmarkAssertions {
+                            expect_exit_code: target.expect_exit_code,
+                            expect_stdout_sha256: target.expect_stdout_sha256.clone(),
+                            expect_stdout_regex: target.expect_stdout_regex.clone(),
+                        },
+                        stdio: BenchmarkStdio {
+                            stdin: target
+                                .stdin
+                                .as_deref()
+                                .map(substitute)
+                                .transpose()?
+                                .map(|stdin| config_dir.join(stdin)),
+                            stdout: target.stdout.map(to_harness_output_target),
+                            stderr: target.stderr.map(to_harness_output_target),
+                        },
+                        params,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
         })
+        .flatten_ok()
         .collect::<Result<Vec<_>>>()?;
 
     serde_json::to_string(&inputs).context("Failed to serialize targets to JSON")
 }
 
+fn to_harness_output_target(target: OutputTarget) -> exec_harness::stdio::OutputTarget {
+    match target {
+        OutputTarget::Inherit => exec_harness::stdio::OutputTarget::Inherit,
+        OutputTarget::Null => exec_harness::stdio::OutputTarget::Null,
+        OutputTarget::File => exec_harness::stdio::OutputTarget::File,
+    }
+}
+
 /// Merge default walltime options with target-specific overrides
-fn merge_walltime_options(
+pub fn merge_walltime_options(
     default: Option<&WalltimeOptions>,
     target: Option<&WalltimeOptions>,
 ) -> exec_harness::walltime::WalltimeExecutionArgs {
@@ -49,6 +208,23 @@ fn merge_walltime_options(
             min_time: t.min_time.or(d.min_time),
             max_rounds: t.max_rounds.or(d.max_rounds),
             min_rounds: t.min_rounds.or(d.min_rounds),
+            iters_per_round: t.iters_per_round.or(d.iters_per_round),
+            spawn_overhead: None,
+            target_precision: t.target_precision.or(d.target_precision),
+            process_settings: exec_harness::walltime::ProcessSettingsArgs {
+                cpu_affinity: t
+                    .process_settings
+                    .cpu_affinity
+                    .or(d.process_settings.cpu_affinity),
+                nice: t.process_settings.nice.or(d.process_settings.nice),
+                sched_fifo: t
+                    .process_settings
+                    .sched_fifo
+                    .or(d.process_settings.sched_fifo),
+                disable_aslr: target
+                    .and_then(|o| o.disable_aslr)
+                    .unwrap_or(d.process_settings.disable_aslr),
+            },
         },
     }
 }
@@ -63,22 +239,408 @@ fn walltime_options_to_args(
         min_time: opts.min_time.clone(),
         max_rounds: opts.max_rounds,
         min_rounds: opts.min_rounds,
+        iters_per_round: opts.iters_per_round.map(|i| i.to_string()),
+        // Only available from the exec command line
+        spawn_overhead: None,
+        target_precision: opts.target_precision.clone(),
+        process_settings: exec_harness::walltime::ProcessSettingsArgs {
+            cpu_affinity: opts.cpu_affinity.clone(),
+            nice: opts.nice,
+            sched_fifo: opts.sched_fifo,
+            disable_aslr: opts.disable_aslr.unwrap_or(false),
+        },
+    }
+}
+
+/// Build the command running the targets of a mode: the `run` targets first, one after the
+/// other with their own harness, then the `exec` targets piped to exec-harness
+pub fn build_targets_command(
+    targets: &[&Target],
+    default_walltime: Option<&WalltimeOptions>,
+    scheduling_args: &SchedulingArgs,
+) -> Result<Vec<String>> {
+    let (run_targets, exec_targets): (Vec<&Target>, Vec<&Target>) =
+        targets.iter().partition(|target| target.run.is_some());
+
+    let mut command = vec![];
+    if !run_targets.is_empty() {
+        // Stop at the first failing harness
+        command.push("set -e\n".to_owned());
+        let config_dir = std::env::current_dir().context("Failed to get current directory")?;
+        for target in run_targets {
+            command.push(format!("{}\n", run_target_command(target, &config_dir)));
+        }
+    }
+    if !exec_targets.is_empty() {
+        command.extend(build_pipe_command(
+            &exec_targets,
+            default_walltime,
+            scheduling_args,
+        )?);
+    }
+
+    Ok(command)
+}
+
+/// Shell command of a `run` target, run in a subshell with the environment and working directory
+/// of the target
+fn run_target_command(target: &Target, config_dir: &Path) -> String {
+    let mut steps = vec![];
+    if let Some(env) = target.env.as_ref().filter(|env| !env.is_empty()) {
+        steps.push(format!(
+            "export {}",
+            env.iter()
+                .map(|(key, value)| format!("{key}={}", shell_words::quote(value)))
+                .join(" ")
+        ));
+    }
+    if let Some(working_directory) = &target.working_directory {
+        steps.push(format!(
+            "cd {}",
+            shell_words::quote(&config_dir.join(working_directory).to_string_lossy())
+        ));
     }
+    steps.push(target.command().to_owned());
+
+    format!("({})", steps.join(" && "))
 }
 
 /// Build a command that pipes targets JSON to exec-harness via stdin
 pub fn build_pipe_command(
-    targets: &[Target],
+    targets: &[&Target],
     default_walltime: Option<&WalltimeOptions>,
+    scheduling_args: &SchedulingArgs,
 ) -> Result<Vec<String>> {
     let json = targets_to_exec_harness_json(targets, default_walltime)?;
     // Use a heredoc to safely pass the JSON to exec-harness
-    Ok(vec![
-        EXEC_HARNESS_COMMAND.to_owned(),
+    let mut command = vec![EXEC_HARNESS_COMMAND.to_owned()];
+    command.extend(scheduling_args.to_cli_args());
+    command.extend([
         "-".to_owned(),
         "<<".to_owned(),
         "'CODSPEED_EOF'\n".to_owned(),
         json,
         "\nCODSPEED_EOF".to_owned(),
-    ])
+    ]);
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_config::ProjectConfig;
+
+    #[test]
+    fn test_targets_to_exec_harness_json() {
+        let config: ProjectConfig = serde_yaml::from_str(
+            r#"
+benchmarks:
+  - name: parse
+    exec: ./parse --threads 4
+    env:
+      DATASET: large.json
+    working-directory: bench
+    timeout: 30s
+    setup: ./generate-dataset large.json
+    before-each: rm -rf cache
+    expect-exit-code: 1
+    expect-stdout-regex: "^parsed"
+    stdin: data/{{size}}.json
+    stdout: "null"
+    matrix:
+      size: [large]
+  - exec: ./serialize
+"#,
+        )
+        .unwrap();
+        let targets = config
+            .benchmarks
+            .as_ref()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        let json = targets_to_exec_harness_json(&targets, None).unwrap();
+        let commands: Vec<BenchmarkCommand> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(commands[0].command, vec!["./parse", "--threads", "4"]);
+        assert_eq!(commands[0].env["DATASET"], "large.json");
+        assert_eq!(
+            commands[0].working_directory,
+            Some(std::env::current_dir().unwrap().join("bench"))
+        );
+        assert_eq!(commands[0].timeout.as_deref(), Some("30s"));
+        assert_eq!(
+            commands[0].hooks,
+            BenchmarkHooks {
+                setup: Some("./generate-dataset large.json".into()),
+                before_each: Some("rm -rf cache".into()),
+                ..Default::default()
+            }
+        );
+
+        assert!(commands[1].env.is_empty());
+        assert_eq!(commands[1].working_directory, None);
+        assert_eq!(
+            commands[0].assertions,
+            BenchmarkAssertions {
+                expect_exit_code: Some(1),
+                expect_stdout_regex: Some("^parsed".into()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            commands[0].stdio,
+            BenchmarkStdio {
+                stdin: Some(std::env::current_dir().unwrap().join("data/large.json")),
+                stdout: Some(exec_harness::stdio::OutputTarget::Null),
+                stderr: None,
+            }
+        );
+        assert_eq!(commands[1].timeout, None);
+        assert!(commands[1].hooks.is_empty());
+    }
+
+    #[test]
+    fn test_group_targets_by_mode() {
+        let config: ProjectConfig = serde_yaml::from_str(
+            r#"
+modes: [simulation]
+benchmarks:
+  - name: io
+    exec: ./io
+    modes: [walltime]
+  - name: parse
+    exec: ./parse
+  - name: alloc
+    exec: ./alloc
+    modes: [memory, simulation]
+"#,
+        )
+        .unwrap();
+        let targets = config
+            .benchmarks
+            .as_ref()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        let groups = group_targets_by_mode(&targets, config.modes.as_deref(), None, || {
+            bail!("the default mode is not needed")
+        })
+        .unwrap();
+        let groups = groups
+            .iter()
+            .map(|(mode, targets)| {
+                (
+                    mode.clone(),
+                    targets.iter().map(|t| t.display_name()).collect(),
+                )
+            })
+            .collect::<Vec<(RunnerMode, Vec<&str>)>>();
+
+        assert_eq!(
+            groups,
+            vec![
+                (RunnerMode::Walltime, vec!["io"]),
+                (RunnerMode::Simulation, vec!["parse", "alloc"]),
+                (RunnerMode::Memory, vec!["alloc"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_targets_by_mode_uses_run_mode() {
+        let config: ProjectConfig = serde_yaml::from_str(
+            r#"
+benchmarks:
+  - exec: ./io
+    modes: [walltime]
+  - exec: ./parse
+  - exec: ./empty
+    modes: []
+"#,
+        )
+        .unwrap();
+        let targets = config
+            .benchmarks
+            .as_ref()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        let groups =
+            group_targets_by_mode(&targets[..2], None, None, || Ok(RunnerMode::Walltime)).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].1.len(), 2);
+
+        assert!(group_targets_by_mode(&targets[..2], None, None, || bail!("no mode")).is_err());
+        assert!(group_targets_by_mode(&targets, None, None, || Ok(RunnerMode::Walltime)).is_err());
+    }
+
+    #[test]
+    fn test_group_targets_by_mode_uses_explicit_mode() {
+        let config: ProjectConfig = serde_yaml::from_str(
+            r#"
+modes: [simulation, memory]
+benchmarks:
+  - exec: ./io
+    modes: [walltime]
+  - exec: ./parse
+"#,
+        )
+        .unwrap();
+        let targets = config
+            .benchmarks
+            .as_ref()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        let groups = group_targets_by_mode(
+            &targets,
+            config.modes.as_deref(),
+            Some(RunnerMode::Memory),
+            || bail!("the default mode is not needed"),
+        )
+        .unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].0, RunnerMode::Memory);
+        assert_eq!(
+            groups[0]
+                .1
+                .iter()
+                .map(|t| t.display_name())
+                .collect::<Vec<_>>(),
+            vec!["./parse"]
+        );
+
+        let groups = group_targets_by_mode(
+            &targets,
+            config.modes.as_deref(),
+            Some(RunnerMode::Walltime),
+            || bail!("the default mode is not needed"),
+        )
+        .unwrap();
+        assert_eq!(
+            groups[0]
+                .1
+                .iter()
+                .map(|t| t.display_name())
+                .collect::<Vec<_>>(),
+            vec!["./io"]
+        );
+
+        assert!(
+            group_targets_by_mode(
+                &targets[1..],
+                config.modes.as_deref(),
+                Some(RunnerMode::Walltime),
+                || bail!("the default mode is not needed"),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_build_targets_command() {
+        let config: ProjectConfig = serde_yaml::from_str(
+            r#"
+benchmarks:
+  - exec: ./parse
+  - name: criterion
+    run: cargo codspeed run
+    env:
+      RUSTFLAGS: -C target-cpu=native
+    working-directory: crates/core
+  - run: pytest --codspeed
+"#,
+        )
+        .unwrap();
+        let targets = config
+            .benchmarks
+            .as_ref()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        let config_dir = std::env::current_dir().unwrap();
+
+        let command = build_targets_command(&targets, None, &SchedulingArgs::default())
+            .unwrap()
+            .join(" ");
+        let lines = command.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(lines[0], "set -e");
+        assert_eq!(
+            lines[1],
+            format!(
+                "(export RUSTFLAGS='-C target-cpu=native' && cd {} && cargo codspeed run)",
+                config_dir.join("crates/core").display()
+            )
+        );
+        assert_eq!(lines[2], "(pytest --codspeed)");
+        assert!(lines[3].starts_with("exec-harness - <<"));
+
+        // Without `exec` targets, exec-harness is not needed
+        let command = build_targets_command(&targets[2..], None, &SchedulingArgs::default())
+            .unwrap()
+            .join(" ");
+        assert!(!command.contains(EXEC_HARNESS_COMMAND));
+
+        // The scheduling args apply to all the `exec` targets
+        let scheduling_args = SchedulingArgs {
+            shuffle_seed: Some(42),
+            ..Default::default()
+        };
+        let command = build_targets_command(&targets[..1], None, &scheduling_args)
+            .unwrap()
+            .join(" ");
+        assert!(command.starts_with("exec-harness --shuffle-seed 42 - <<"));
+    }
+
+    #[test]
+    fn test_targets_to_exec_harness_json_expands_matrix() {
+        let config: ProjectConfig = serde_yaml::from_str(
+            r#"
+benchmarks:
+  - name: parse
+    exec: ./parse --input data/{{size}}.json
+    env:
+      THREADS: "{{threads}}"
+    matrix:
+      size: [1k, 1M]
+      threads: [1, 8]
+"#,
+        )
+        .unwrap();
+        let targets = config
+            .benchmarks
+            .as_ref()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        let json = targets_to_exec_harness_json(&targets, None).unwrap();
+        let commands: Vec<BenchmarkCommand> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(commands.len(), 4);
+        assert!(commands.iter().all(|c| c.name.as_deref() == Some("parse")));
+        assert_eq!(
+            commands[1].command,
+            vec!["./parse", "--input", "data/1k.json"]
+        );
+        assert_eq!(commands[1].env["THREADS"], "8");
+        assert_eq!(
+            commands[1].params,
+            [
+                ("size".to_string(), "1k".to_string()),
+                ("threads".to_string(), "8".to_string())
+            ]
+            .into()
+        );
+        assert_eq!(
+            commands[2].command,
+            vec!["./parse", "--input", "data/1M.json"]
+        );
+        assert_eq!(commands[2].env["THREADS"], "1");
+    }
 }
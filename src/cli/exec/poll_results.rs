@@ -1,7 +1,11 @@
 use console::style;
+use std::path::Path;
 
 use crate::api_client::CodSpeedAPIClient;
 use crate::cli::run::helpers::benchmark_display::{build_benchmark_table, build_detailed_summary};
+use crate::cli::run::poll_results::{
+    log_benchmarks_ran_json, log_resource_usage_table, log_run_finished_json,
+};
 use crate::prelude::*;
 use crate::upload::{UploadResult, poll_run_report};
 
@@ -9,9 +13,15 @@ use crate::upload::{UploadResult, poll_run_report};
 pub async fn poll_results(
     api_client: &CodSpeedAPIClient,
     upload_result: &UploadResult,
+    profile_folder: &Path,
+    output_json: bool,
 ) -> Result<()> {
     let response = poll_run_report(api_client, upload_result).await?;
 
+    if output_json {
+        log_run_finished_json(upload_result);
+    }
+
     if !response.run.results.is_empty() {
         end_group!();
         start_group!("Benchmark results");
@@ -23,6 +33,11 @@ pub async fn poll_results(
             let table = build_benchmark_table(&response.run.results);
             info!("\n{table}");
         }
+        log_resource_usage_table(profile_folder);
+
+        if output_json {
+            log_benchmarks_ran_json(&response.run.results);
+        }
 
         info!(
             "\nTo see the full report, visit: {}",
@@ -0,0 +1,293 @@
+use crate::prelude::*;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Maximum depth of the directories inspected below the repository root
+const MAX_DEPTH: usize = 4;
+
+/// Directories that never contain benchmarks of the project itself
+const IGNORED_DIRECTORIES: &[&str] = &["target", "node_modules", "vendor", "venv", "dist"];
+
+/// Directories in which executables are considered to be benchmarks
+const BENCHMARK_DIRECTORIES: &[&str] = &["bench", "benches", "benchmark", "benchmarks"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framework {
+    Criterion,
+    Divan,
+    Pytest,
+    Vitest,
+    GoTest,
+    Executable,
+}
+
+impl Framework {
+    /// Whether the benchmarks are instrumented by a CodSpeed integration, in which case they
+    /// have to be run with `codspeed run` rather than through the exec harness
+    pub fn has_integration(&self) -> bool {
+        !matches!(self, Framework::Executable)
+    }
+}
+
+impl std::fmt::Display for Framework {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Framework::Criterion => write!(f, "criterion"),
+            Framework::Divan => write!(f, "divan"),
+            Framework::Pytest => write!(f, "pytest"),
+            Framework::Vitest => write!(f, "vitest"),
+            Framework::GoTest => write!(f, "go test"),
+            Framework::Executable => write!(f, "executable"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedBenchmark {
+    pub framework: Framework,
+    /// Directory the command has to be run from, relative to the repository root
+    pub directory: PathBuf,
+    /// Command running the benchmarks
+    pub command: String,
+}
+
+/// Inspect the repository to find the benchmarks it contains
+pub fn detect_benchmarks(root: &Path) -> Result<Vec<DetectedBenchmark>> {
+    let mut detected: Vec<DetectedBenchmark> = vec![];
+    let mut add = |framework: Framework, directory: &Path, command: String| {
+        let directory = directory
+            .strip_prefix(root)
+            .unwrap_or(directory)
+            .to_path_buf();
+        // A workspace and its members share the same command, keep the outermost one
+        let is_nested = framework.has_integration()
+            && detected
+                .iter()
+                .any(|d| d.framework == framework && directory.starts_with(&d.directory));
+        if !is_nested {
+            detected.push(DetectedBenchmark {
+                framework,
+                directory,
+                command,
+            });
+        }
+    };
+
+    let mut directories = vec![(root.to_path_buf(), 0)];
+    while let Some((directory, depth)) = directories.pop() {
+        let mut entries = fs::read_dir(&directory)
+            .with_context(|| format!("Failed to read directory {}", directory.display()))?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        let read = |file_name: &str| fs::read_to_string(directory.join(file_name)).ok();
+
+        if let Some(manifest) = read("Cargo.toml") {
+            if manifest.contains("criterion") {
+                add(
+                    Framework::Criterion,
+                    &directory,
+                    "cargo codspeed run".into(),
+                );
+            }
+            if manifest.contains("divan") {
+                add(Framework::Divan, &directory, "cargo codspeed run".into());
+            }
+        }
+
+        let uses_pytest = [
+            "pyproject.toml",
+            "pytest.ini",
+            "setup.cfg",
+            "requirements.txt",
+        ]
+        .iter()
+        .filter_map(|file_name| read(file_name))
+        .any(|content| content.contains("pytest"));
+        if uses_pytest {
+            add(Framework::Pytest, &directory, "pytest --codspeed".into());
+        }
+
+        if read("package.json").is_some_and(|content| content.contains("\"vitest\"")) {
+            add(
+                Framework::Vitest,
+                &directory,
+                "npx vitest bench --run".into(),
+            );
+        }
+
+        if directory.join("go.mod").exists() && has_go_benchmarks(&directory, depth) {
+            add(
+                Framework::GoTest,
+                &directory,
+                "go test -bench=. ./...".into(),
+            );
+        }
+
+        let is_benchmark_directory = directory
+            .file_name()
+            .is_some_and(|name| BENCHMARK_DIRECTORIES.contains(&name.to_string_lossy().as_ref()));
+        for path in &entries {
+            if is_benchmark_directory && is_executable(path) {
+                let relative_path = path.strip_prefix(root).unwrap_or(path);
+                add(
+                    Framework::Executable,
+                    root,
+                    format!("./{}", relative_path.display()),
+                );
+            }
+
+            if depth < MAX_DEPTH && path.is_dir() && !is_ignored(path) {
+                directories.push((path.clone(), depth + 1));
+            }
+        }
+    }
+
+    detected.sort_by(|a, b| a.directory.cmp(&b.directory));
+    for benchmark in &mut detected {
+        if benchmark.directory.as_os_str().is_empty() {
+            benchmark.directory = PathBuf::from(".");
+        }
+    }
+    Ok(detected)
+}
+
+fn is_ignored(path: &Path) -> bool {
+    path.file_name().is_none_or(|name| {
+        let name = name.to_string_lossy();
+        name.starts_with('.') || IGNORED_DIRECTORIES.contains(&name.as_ref())
+    })
+}
+
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+/// Whether a Go module contains `func Benchmark...` functions in its test files
+fn has_go_benchmarks(directory: &Path, depth: usize) -> bool {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return false;
+    };
+
+    entries.filter_map(Result::ok).any(|entry| {
+        let path = entry.path();
+        if path.is_dir() {
+            depth < MAX_DEPTH && !is_ignored(&path) && has_go_benchmarks(&path, depth + 1)
+        } else {
+            path.to_string_lossy().ends_with("_test.go")
+                && fs::read_to_string(&path).is_ok_and(|content| content.contains("func Benchmark"))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_detect_cargo_workspace() {
+        let root = TempDir::new().unwrap();
+        write(
+            root.path(),
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        );
+        write(
+            root.path(),
+            "crates/a/Cargo.toml",
+            "[dev-dependencies]\ncriterion = { package = \"codspeed-criterion-compat\", version = \"*\" }\n",
+        );
+        write(
+            root.path(),
+            "crates/b/Cargo.toml",
+            "[dev-dependencies]\ndivan = \"0.1\"\n",
+        );
+
+        let detected = detect_benchmarks(root.path()).unwrap();
+        assert_eq!(
+            detected
+                .iter()
+                .map(|d| (d.framework, d.directory.to_str().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Framework::Criterion, "crates/a"),
+                (Framework::Divan, "crates/b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_multiple_languages() {
+        let root = TempDir::new().unwrap();
+        write(
+            root.path(),
+            "pyproject.toml",
+            "[project.optional-dependencies]\ntest = [\"pytest-codspeed\"]\n",
+        );
+        write(
+            root.path(),
+            "web/package.json",
+            r#"{"devDependencies": {"vitest": "^3.0.0"}}"#,
+        );
+        write(root.path(), "go/go.mod", "module example.com/bench\n");
+        write(
+            root.path(),
+            "go/pkg/sort_test.go",
+            "package pkg\n\nfunc BenchmarkSort(b *testing.B) {}\n",
+        );
+        write(
+            root.path(),
+            "web/node_modules/dep/package.json",
+            "\"vitest\"",
+        );
+
+        let detected = detect_benchmarks(root.path()).unwrap();
+        assert_eq!(
+            detected
+                .iter()
+                .map(|d| (
+                    d.framework,
+                    d.directory.to_str().unwrap(),
+                    d.command.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (Framework::Pytest, ".", "pytest --codspeed"),
+                (Framework::GoTest, "go", "go test -bench=. ./..."),
+                (Framework::Vitest, "web", "npx vitest bench --run"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_executables() {
+        let root = TempDir::new().unwrap();
+        write(root.path(), "benches/run.sh", "#!/bin/sh\n");
+        write(root.path(), "benches/README.md", "");
+        write(root.path(), "scripts/not_a_bench.sh", "#!/bin/sh\n");
+        for path in ["benches/run.sh", "scripts/not_a_bench.sh"] {
+            fs::set_permissions(root.path().join(path), fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let detected = detect_benchmarks(root.path()).unwrap();
+        assert_eq!(
+            detected,
+            vec![DetectedBenchmark {
+                framework: Framework::Executable,
+                directory: ".".into(),
+                command: "./benches/run.sh".into(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::cli::run::helpers::find_repository_root;
+use crate::prelude::*;
+use crate::project_config::{
+    CONFIG_FILENAMES, ProjectConfig, ProjectOptions, Target, WalltimeOptions,
+};
+use clap::Args;
+use console::style;
+use std::path::Path;
+
+mod detect;
+
+use detect::{DetectedBenchmark, detect_benchmarks};
+
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    /// Overwrite the existing configuration file
+    #[arg(long)]
+    pub force: bool,
+}
+
+pub fn run(args: InitArgs) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let root = find_repository_root(&current_dir).unwrap_or(current_dir);
+
+    if !args.force {
+        if let Some(existing) = CONFIG_FILENAMES
+            .iter()
+            .map(|filename| root.join(filename))
+            .find(|path| path.exists())
+        {
+            bail!(
+                "A configuration file already exists at {}, use --force to overwrite it",
+                existing.display()
+            );
+        }
+    }
+
+    let detected = detect_benchmarks(&root)?;
+    for benchmark in &detected {
+        info!(
+            "Detected {} benchmarks in {}",
+            style(benchmark.framework).bold(),
+            benchmark.directory.display()
+        );
+    }
+
+    let content = render_config(&detected)?;
+    let config_path = root.join(CONFIG_FILENAMES[0]);
+    std::fs::write(&config_path, content)
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    info!("Configuration written to {}", config_path.display());
+
+    Ok(())
+}
+
+/// Build the `codspeed.yaml` content from the detected benchmarks.
+///
+/// Benchmarks relying on a CodSpeed integration are `run` targets executing their harness, the
+/// other ones are `exec` targets measured by the exec harness.
+fn render_config(detected: &[DetectedBenchmark]) -> Result<String> {
+    let targets = detected
+        .iter()
+        .map(|benchmark| {
+            let is_root = matches!(benchmark.directory.to_str(), Some("" | "."));
+            let (name, exec, run, working_directory) = if benchmark.framework.has_integration() {
+                let name = if is_root {
+                    benchmark.framework.to_string()
+                } else {
+                    format!("{} {}", benchmark.framework, benchmark.directory.display())
+                };
+                let working_directory =
+                    (!is_root).then(|| benchmark.directory.to_string_lossy().into_owned());
+                (
+                    Some(name),
+                    None,
+                    Some(benchmark.command.clone()),
+                    working_directory,
+                )
+            } else {
+                let name = Path::new(&benchmark.command)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned());
+                (name, Some(benchmark.command.clone()), None, None)
+            };
+
+            Target {
+                name,
+                exec,
+                run,
+                env: None,
+                working_directory,
+                timeout: None,
+                matrix: None,
+                modes: None,
+                setup: None,
+                teardown: None,
+                before_each: None,
+                after_each: None,
+                expect_exit_code: None,
+                expect_stdout_sha256: None,
+                expect_stdout_regex: None,
+                stdin: None,
+                stdout: None,
+                stderr: None,
+                options: None,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let config = ProjectConfig {
+        extends: None,
+        include: None,
+        options: Some(ProjectOptions {
+            working_directory: None,
+            walltime: Some(WalltimeOptions {
+                warmup_time: Some("1s".into()),
+                max_time: Some("3s".into()),
+                min_time: None,
+                max_rounds: None,
+                min_rounds: None,
+                iters_per_round: None,
+                target_precision: None,
+                cpu_affinity: None,
+                nice: None,
+                sched_fifo: None,
+                disable_aslr: None,
+            }),
+            ..Default::default()
+        }),
+        benchmarks: (!targets.is_empty()).then_some(targets),
+        modes: None,
+        profiles: None,
+    };
+    config.validate()?;
+
+    let mut content = String::from("# CodSpeed configuration, generated by `codspeed init`\n");
+    content.push_str(&serde_yaml::to_string(&config)?);
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect::Framework;
+    use super::*;
+
+    #[test]
+    fn test_render_config() {
+        let content = render_config(&[
+            DetectedBenchmark {
+                framework: Framework::Criterion,
+                directory: ".".into(),
+                command: "cargo codspeed run".into(),
+            },
+            DetectedBenchmark {
+                framework: Framework::Pytest,
+                directory: "python".into(),
+                command: "pytest --codspeed".into(),
+            },
+            DetectedBenchmark {
+                framework: Framework::Executable,
+                directory: ".".into(),
+                command: "./benches/run.sh".into(),
+            },
+        ])
+        .unwrap();
+
+        insta::assert_snapshot!(content, @r"
+        # CodSpeed configuration, generated by `codspeed init`
+        options:
+          warmup-time: 1s
+          max-time: 3s
+        benchmarks:
+        - name: criterion
+          run: cargo codspeed run
+        - name: pytest python
+          run: pytest --codspeed
+          working-directory: python
+        - name: run.sh
+          exec: ./benches/run.sh
+        ");
+
+        let config: ProjectConfig = serde_yaml::from_str(&content).unwrap();
+        assert!(config.validate().is_ok());
+    }
+}
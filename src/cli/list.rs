@@ -0,0 +1,100 @@
+use crate::cli::exec::multi_targets::{filter_targets, merge_walltime_options};
+use crate::prelude::*;
+use crate::project_config::{ProjectConfig, Target, WalltimeOptions};
+use clap::Args;
+use exec_harness::filter::BenchmarkFilterArgs;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    #[command(flatten)]
+    pub filter_args: BenchmarkFilterArgs,
+}
+
+#[derive(Debug, PartialEq, Tabled)]
+struct TargetRow {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Command")]
+    command: String,
+    #[tabled(rename = "Options")]
+    options: String,
+}
+
+pub fn run(args: ListArgs, project_config: Option<&ProjectConfig>) -> Result<()> {
+    let targets = project_config
+        .and_then(|c| c.benchmarks.as_ref())
+        .filter(|targets| !targets.is_empty())
+        .context("No targets defined in codspeed.yaml")?;
+    let default_walltime = project_config
+        .and_then(|c| c.options.as_ref())
+        .and_then(|o| o.walltime.as_ref());
+
+    let targets = filter_targets(targets, &args.filter_args)?;
+    let rows = build_rows(&targets, default_walltime);
+    info!("{}", Table::new(rows).with(Style::rounded()));
+
+    Ok(())
+}
+
+fn build_rows(targets: &[&Target], default_walltime: Option<&WalltimeOptions>) -> Vec<TargetRow> {
+    targets
+        .iter()
+        .map(|target| {
+            let target_walltime = target.options.as_ref().and_then(|o| o.walltime.as_ref());
+            TargetRow {
+                name: target.display_name().to_string(),
+                command: target.command().to_owned(),
+                options: merge_walltime_options(default_walltime, target_walltime)
+                    .to_cli_args()
+                    .join(" "),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_rows() {
+        let config: ProjectConfig = serde_yaml::from_str(
+            r#"
+options:
+  warmup-time: 1s
+benchmarks:
+  - name: parse
+    exec: ./parse input.json
+    options:
+      max-rounds: 10
+  - exec: ./serialize
+"#,
+        )
+        .unwrap();
+        let targets = config
+            .benchmarks
+            .as_ref()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        let default_walltime = config.options.as_ref().and_then(|o| o.walltime.as_ref());
+
+        assert_eq!(
+            build_rows(&targets, default_walltime),
+            vec![
+                TargetRow {
+                    name: "parse".into(),
+                    command: "./parse input.json".into(),
+                    options: "--warmup-time 1s --max-rounds 10".into(),
+                },
+                TargetRow {
+                    name: "./serialize".into(),
+                    command: "./serialize".into(),
+                    options: "--warmup-time 1s".into(),
+                },
+            ]
+        );
+    }
+}
@@ -1,9 +1,15 @@
 mod auth;
+mod compare;
+mod config;
+mod doctor;
 pub(crate) mod exec;
+mod init;
+mod list;
 pub(crate) mod run;
 mod setup;
 mod shared;
 mod show;
+pub(crate) mod upload;
 mod use_mode;
 
 pub(crate) use shared::*;
@@ -18,7 +24,7 @@ use crate::{
     project_config::ProjectConfig,
 };
 use clap::{
-    Parser, Subcommand,
+    CommandFactory, FromArgMatches, Parser, Subcommand,
     builder::{Styles, styling},
 };
 
@@ -61,6 +67,10 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub config: Option<PathBuf>,
 
+    /// The profile of the project configuration file to use, overriding its root options
+    #[arg(long, env = "CODSPEED_PROFILE", global = true)]
+    pub profile: Option<String>,
+
     /// The directory to use for caching installed tools
     /// The runner will restore cached tools from this directory before installing them.
     /// After successful installation, the runner will cache the installed tools to this directory.
@@ -89,17 +99,48 @@ enum Commands {
     Use(use_mode::UseArgs),
     /// Show the codspeed mode previously set in this shell session with `codspeed use`
     Show,
+    /// Compare the results of two local profile folders, without uploading them to CodSpeed
+    Compare(compare::CompareArgs),
+    /// Upload the results of an existing profile folder to CodSpeed, without running the
+    /// benchmarks
+    Upload(Box<upload::UploadArgs>),
+    /// Diagnose the environment and report the issues that would prevent each executor from
+    /// running
+    Doctor(doctor::DoctorArgs),
+    /// Create a codspeed.yaml configuration file from the benchmarks detected in the repository
+    Init(init::InitArgs),
+    /// Inspect the project configuration file (codspeed.yaml)
+    Config(config::ConfigArgs),
+    /// List the benchmark targets defined in codspeed.yaml with their effective options
+    List(list::ListArgs),
 }
 
 pub async fn run() -> Result<()> {
-    let cli = Cli::parse();
+    // Keep the matches around to know where the argument values come from
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
     let codspeed_config =
         CodSpeedConfig::load_with_override(cli.config_name.as_deref(), cli.oauth_token.as_deref())?;
     let api_client = CodSpeedAPIClient::try_from((&cli, &codspeed_config))?;
 
     // Discover project configuration file (this may change the working directory)
-    let project_config =
-        ProjectConfig::discover_and_load(cli.config.as_deref(), &std::env::current_dir()?)?;
+    // Only the commands consuming it load it, so that the others keep working with a broken file
+    let project_config = match cli.command {
+        Commands::Run(_) | Commands::Exec(_) | Commands::List(_) => {
+            let project_config =
+                ProjectConfig::discover_and_load(cli.config.as_deref(), &std::env::current_dir()?)?;
+            match (project_config, cli.profile.as_deref()) {
+                (Some(project_config), Some(profile)) => {
+                    Some(project_config.with_profile(profile)?)
+                }
+                (None, Some(profile)) => {
+                    bail!("The profile `{profile}` is selected but no codspeed.yaml was found")
+                }
+                (project_config, None) => project_config,
+            }
+        }
+        _ => None,
+    };
 
     // In the context of the CI, it is likely that a ~ made its way here without being expanded by the shell
     let setup_cache_dir = cli
@@ -109,7 +150,7 @@ pub async fn run() -> Result<()> {
     let setup_cache_dir = setup_cache_dir.as_deref();
 
     match cli.command {
-        Commands::Run(_) | Commands::Exec(_) => {} // Run and Exec are responsible for their own logger initialization
+        Commands::Run(_) | Commands::Exec(_) | Commands::Upload(_) => {} // Run, Exec and Upload are responsible for their own logger initialization
         _ => {
             init_local_logger()?;
         }
@@ -140,6 +181,17 @@ pub async fn run() -> Result<()> {
         Commands::Setup => setup::setup(setup_cache_dir).await?,
         Commands::Use(args) => use_mode::run(args)?,
         Commands::Show => show::run()?,
+        Commands::Compare(args) => compare::run(args)?,
+        Commands::Upload(args) => upload::run(*args, &api_client, &codspeed_config).await?,
+        Commands::Doctor(args) => doctor::run(args)?,
+        Commands::Init(args) => init::run(args)?,
+        Commands::Config(args) => config::run(
+            args,
+            &matches,
+            cli.config.as_deref(),
+            cli.profile.as_deref(),
+        )?,
+        Commands::List(args) => list::run(args, project_config.as_ref())?,
     }
     Ok(())
 }
@@ -153,6 +205,7 @@ impl Cli {
             oauth_token: None,
             config_name: None,
             config: None,
+            profile: None,
             setup_cache_dir: None,
             command: Commands::Setup,
         }
@@ -1,4 +1,5 @@
 use crate::api_client::FetchLocalRunBenchmarkResult;
+use crate::cli::compare::profile_results::WalltimeEntry;
 use crate::cli::run::helpers;
 use crate::executor::ExecutorName;
 use std::collections::HashMap;
@@ -62,7 +63,27 @@ struct MemoryRow {
     alloc_calls: String,
 }
 
-fn build_table_with_style<T: Tabled>(rows: &[T], instrument: &str) -> String {
+#[derive(Tabled)]
+struct ResourceUsageRow {
+    #[tabled(rename = "Benchmark")]
+    name: String,
+    #[tabled(rename = "User time")]
+    user_time: String,
+    #[tabled(rename = "Sys. time")]
+    system_time: String,
+    #[tabled(rename = "Max RSS")]
+    max_rss: String,
+    #[tabled(rename = "Faults (minor/major)")]
+    faults: String,
+    #[tabled(rename = "Ctx switches (vol./invol.)")]
+    context_switches: String,
+}
+
+pub(crate) fn build_table_with_style<T: Tabled>(rows: &[T], instrument: &str) -> String {
+    build_table_with_title(rows, &format!("{instrument} Instrument"))
+}
+
+fn build_table_with_title<T: Tabled>(rows: &[T], title: &str) -> String {
     // Line after panel header: use ┬ to connect with columns below
     let header_line = HorizontalLine::full('─', '┬', '├', '┤');
     // Line after column headers: keep intersection
@@ -71,7 +92,7 @@ fn build_table_with_style<T: Tabled>(rows: &[T], instrument: &str) -> String {
     // Format title in bold CodSpeed orange (#FF8700)
     let codspeed_orange = Color::rgb_fg(255, 135, 0);
     let title_style = Color::BOLD | codspeed_orange;
-    let title = title_style.colorize(format!("{instrument} Instrument"));
+    let title = title_style.colorize(title);
 
     let mut table = Table::new(rows);
     table
@@ -223,6 +244,35 @@ pub fn build_benchmark_table(results: &[FetchLocalRunBenchmarkResult]) -> String
     output
 }
 
+/// Build a table of the resources used per execution by the walltime benchmarks, as collected
+/// locally by exec-harness
+///
+/// Returns `None` if none of the benchmarks has its resource usage.
+pub fn build_resource_usage_table(entries: &[WalltimeEntry]) -> Option<String> {
+    let rows: Vec<ResourceUsageRow> = entries
+        .iter()
+        .filter_map(|entry| {
+            let usage = entry.resource_usage.as_ref()?;
+            Some(ResourceUsageRow {
+                name: entry.name.clone(),
+                user_time: helpers::format_duration(usage.user_time_ns / 1e9, Some(2)),
+                system_time: helpers::format_duration(usage.system_time_ns / 1e9, Some(2)),
+                max_rss: helpers::format_memory(usage.max_rss_bytes as f64, Some(1)),
+                faults: format!("{:.0} / {:.0}", usage.minor_faults, usage.major_faults),
+                context_switches: format!(
+                    "{:.0} / {:.0}",
+                    usage.voluntary_context_switches, usage.involuntary_context_switches
+                ),
+            })
+        })
+        .collect();
+
+    if rows.is_empty() {
+        return None;
+    }
+    Some(build_table_with_title(&rows, "Resource Usage"))
+}
+
 pub fn build_detailed_summary(result: &FetchLocalRunBenchmarkResult) -> String {
     match result.benchmark.executor {
         ExecutorName::Valgrind => {
@@ -276,6 +326,7 @@ mod tests {
     use crate::api_client::{
         FetchLocalRunBenchmark, MemoryResult, TimeDistribution, ValgrindResult, WallTimeResult,
     };
+    use runner_shared::walltime_results::{BenchmarkStats, ResourceUsage};
 
     #[test]
     fn test_benchmark_table_formatting() {
@@ -440,4 +491,49 @@ mod tests {
         let summary = build_detailed_summary(&result);
         insta::assert_snapshot!(summary, @"benchmark_mem: peak 1 MB (total allocated: 5 MB, 500 allocations)");
     }
+
+    #[test]
+    fn test_resource_usage_table() {
+        let entry = |name: &str, resource_usage: Option<ResourceUsage>| WalltimeEntry {
+            name: name.to_string(),
+            uri: format!("bench.rs::{name}"),
+            stats: BenchmarkStats {
+                min_ns: 1.0,
+                max_ns: 1.0,
+                mean_ns: 1.0,
+                stdev_ns: 0.0,
+                q1_ns: 1.0,
+                median_ns: 1.0,
+                q3_ns: 1.0,
+                rounds: 1,
+                total_time: 1e-9,
+                iqr_outlier_rounds: 0,
+                stdev_outlier_rounds: 0,
+                iter_per_round: 1,
+                warmup_iters: 0,
+            },
+            resource_usage,
+        };
+
+        assert_eq!(build_resource_usage_table(&[entry("bench_a", None)]), None);
+
+        let entries = vec![
+            entry(
+                "bench_parse",
+                Some(ResourceUsage {
+                    user_time_ns: 1_500_000.0,
+                    system_time_ns: 250_000.0,
+                    max_rss_bytes: 8 * 1024 * 1024,
+                    minor_faults: 312.4,
+                    major_faults: 0.0,
+                    voluntary_context_switches: 2.0,
+                    involuntary_context_switches: 1.2,
+                }),
+            ),
+            entry("bench_without_usage", None),
+        ];
+        let table = build_resource_usage_table(&entries).unwrap();
+        let table = console::strip_ansi_codes(&table).to_string();
+        insta::assert_snapshot!(table);
+    }
 }
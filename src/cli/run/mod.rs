@@ -9,13 +9,15 @@ use crate::project_config::merger::ConfigMerger;
 use crate::run_environment::interfaces::RepositoryProvider;
 use crate::upload::UploadResult;
 use clap::{Args, ValueEnum};
+use exec_harness::filter::BenchmarkFilterArgs;
+use exec_harness::walltime::{ProcessSettingsArgs, SchedulingArgs};
 use std::path::Path;
 
 pub mod helpers;
 pub mod logger;
-mod poll_results;
+pub(crate) mod poll_results;
 
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 pub struct RunArgs {
     #[command(flatten)]
     pub shared: ExecAndRunSharedArgs,
@@ -34,6 +36,14 @@ pub struct RunArgs {
     #[arg(long, hide = true)]
     pub message_format: Option<MessageFormat>,
 
+    /// Only applies when running the targets defined in codspeed.yaml
+    #[command(flatten)]
+    pub filter_args: BenchmarkFilterArgs,
+
+    /// Only applies to the walltime mode
+    #[command(flatten)]
+    pub process_settings: ProcessSettingsArgs,
+
     /// The bench command to run
     pub command: Vec<String>,
 }
@@ -44,8 +54,17 @@ impl RunArgs {
     /// CLI arguments take precedence over config values.
     pub fn merge_with_project_config(mut self, project_config: Option<&ProjectConfig>) -> Self {
         if let Some(project_config) = project_config {
-            self.shared =
-                ConfigMerger::merge_shared_args(&self.shared, project_config.options.as_ref());
+            let options = project_config.options.as_ref();
+            self.shared = ConfigMerger::merge_shared_args(&self.shared, options);
+            (self.instruments, self.mongo_uri_env_name) = ConfigMerger::merge_instruments(
+                &self.instruments,
+                &self.mongo_uri_env_name,
+                options,
+            );
+            self.process_settings = ConfigMerger::merge_process_settings(
+                &self.process_settings,
+                options.and_then(|o| o.walltime.as_ref()),
+            );
         }
         self
     }
@@ -78,13 +97,15 @@ impl RunArgs {
                 allow_empty: false,
                 go_runner_version: None,
                 perf_run_args: PerfRunArgs {
-                    enable_perf: false,
+                    enable_perf: Some(false),
                     perf_unwinding_mode: None,
                 },
             },
             instruments: vec![],
             mongo_uri_env_name: None,
             message_format: None,
+            filter_args: BenchmarkFilterArgs::default(),
+            process_settings: ProcessSettingsArgs::default(),
             command: vec![],
         }
     }
@@ -92,16 +113,22 @@ impl RunArgs {
 
 use crate::project_config::Target;
 use crate::project_config::WalltimeOptions;
+use crate::runner_mode::RunnerMode;
 /// Determines the execution mode based on CLI args and project config
 enum RunTarget<'a> {
     /// Single command from CLI args
     SingleCommand(RunArgs),
-    /// Multiple targets from project config
-    /// Note: for now, only `codspeed exec` targets are supported in the project config
+    /// Multiple targets from project config, `exec` targets are piped into exec-harness while
+    /// `run` targets run their own harness
     ConfigTargets {
         args: RunArgs,
-        targets: &'a [Target],
-        default_walltime: Option<&'a WalltimeOptions>,
+        targets: Vec<&'a Target>,
+        default_walltime: Option<WalltimeOptions>,
+        scheduling_args: SchedulingArgs,
+        root_modes: Option<&'a [RunnerMode]>,
+        /// Mode given on the command line or in the environment, before the project config is
+        /// merged
+        explicit_mode: Option<RunnerMode>,
     },
 }
 
@@ -114,6 +141,7 @@ pub async fn run(
 ) -> Result<()> {
     let output_json = args.message_format == Some(MessageFormat::Json);
 
+    let explicit_mode = args.shared.mode.clone();
     let args = args.merge_with_project_config(project_config);
 
     let run_target = if args.command.is_empty() {
@@ -124,65 +152,149 @@ pub async fn run(
             .ok_or_else(|| {
                 anyhow!("No command provided and no targets defined in codspeed.yaml")
             })?;
+        let targets = super::exec::multi_targets::filter_targets(targets, &args.filter_args)?;
 
-        let default_walltime = project_config
-            .and_then(|c| c.options.as_ref())
-            .and_then(|o| o.walltime.as_ref());
+        // The process settings, from the command line or the root walltime options, are applied
+        // to each execution by exec-harness
+        let default_walltime = ConfigMerger::merge_walltime_overrides(
+            project_config
+                .and_then(|c| c.options.as_ref())
+                .and_then(|o| o.walltime.as_ref()),
+            Some(&WalltimeOptions {
+                cpu_affinity: args.process_settings.cpu_affinity.clone(),
+                nice: args.process_settings.nice,
+                sched_fifo: args.process_settings.sched_fifo,
+                disable_aslr: args.process_settings.disable_aslr.then_some(true),
+                ..Default::default()
+            }),
+        );
+        let scheduling_args = ConfigMerger::merge_scheduling_args(
+            &SchedulingArgs::default(),
+            project_config.and_then(|c| c.options.as_ref()),
+        );
 
         RunTarget::ConfigTargets {
             args,
             targets,
             default_walltime,
+            scheduling_args,
+            root_modes: project_config.and_then(|c| c.modes.as_deref()),
+            explicit_mode,
         }
     } else {
+        if !args.filter_args.is_empty() {
+            bail!("--bench and --exclude only apply to the targets defined in codspeed.yaml");
+        }
         RunTarget::SingleCommand(args)
     };
 
     match run_target {
         RunTarget::SingleCommand(args) => {
             let config = Config::try_from(args)?;
-
-            // Create execution context
-            let mut execution_context =
-                executor::ExecutionContext::new(config, codspeed_config, api_client).await?;
-
-            if !execution_context.is_local() {
-                super::show_banner();
-            }
-            debug!("config: {:#?}", execution_context.config);
-
-            // Execute benchmarks
-            let executor = executor::get_executor_from_mode(&execution_context.config.mode);
-
-            let poll_results_fn = async |upload_result: &UploadResult| {
-                poll_results::poll_results(api_client, upload_result, output_json).await
-            };
-            executor::execute_benchmarks(
-                executor.as_ref(),
-                &mut execution_context,
+            execute(
+                config,
+                api_client,
+                codspeed_config,
                 setup_cache_dir,
-                poll_results_fn,
+                output_json,
             )
             .await?;
         }
 
         RunTarget::ConfigTargets {
-            mut args,
+            args,
             targets,
             default_walltime,
+            scheduling_args,
+            root_modes,
+            explicit_mode,
         } => {
-            args.command =
-                super::exec::multi_targets::build_pipe_command(targets, default_walltime)?;
-            let config = Config::try_from(args)?;
+            // Targets are run and uploaded once per mode, the uploads of a CI job are told apart
+            // by their run index
+            let groups = super::exec::multi_targets::group_targets_by_mode(
+                &targets,
+                root_modes,
+                explicit_mode,
+                || args.shared.resolve_mode(),
+            )?;
+            let is_multi_mode = groups.len() > 1;
+
+            for (mode, targets) in groups {
+                if is_multi_mode {
+                    info!("Running {} target(s) in {mode:?} mode", targets.len());
+                }
+                let mut args = args.clone();
+                args.shared.mode = Some(mode);
+                args.command = super::exec::multi_targets::build_targets_command(
+                    &targets,
+                    default_walltime.as_ref(),
+                    &scheduling_args,
+                )?;
+                // Only the benchmark executions run with the process settings, not the harnesses
+                args.process_settings = ProcessSettingsArgs::default();
+                let config = Config::try_from(args)?;
 
-            super::exec::execute_with_harness(config, api_client, codspeed_config, setup_cache_dir)
-                .await?;
+                // The harnesses of `run` targets and exec-harness share the same upload
+                if targets.iter().any(|target| target.exec.is_some()) {
+                    super::exec::execute_with_harness(
+                        config,
+                        api_client,
+                        codspeed_config,
+                        setup_cache_dir,
+                        output_json,
+                    )
+                    .await?;
+                } else {
+                    execute(
+                        config,
+                        api_client,
+                        codspeed_config,
+                        setup_cache_dir,
+                        output_json,
+                    )
+                    .await?;
+                }
+            }
         }
     }
 
     Ok(())
 }
 
+/// Execute the benchmark command of the config with the executor of its mode, and upload the
+/// results
+async fn execute(
+    config: Config,
+    api_client: &CodSpeedAPIClient,
+    codspeed_config: &CodSpeedConfig,
+    setup_cache_dir: Option<&Path>,
+    output_json: bool,
+) -> Result<()> {
+    // Create execution context
+    let mut execution_context =
+        executor::ExecutionContext::new(config, codspeed_config, api_client).await?;
+
+    if !execution_context.is_local() {
+        super::show_banner();
+    }
+    debug!("config: {:#?}", execution_context.config);
+
+    // Execute benchmarks
+    let executor = executor::get_executor_from_mode(&execution_context.config.mode);
+
+    let profile_folder = execution_context.profile_folder.clone();
+    let poll_results_fn = async |upload_result: &UploadResult| {
+        poll_results::poll_results(api_client, upload_result, &profile_folder, output_json).await
+    };
+    executor::execute_benchmarks(
+        executor.as_ref(),
+        &mut execution_context,
+        setup_cache_dir,
+        poll_results_fn,
+    )
+    .await
+}
+
 // We have to implement this manually, because deriving the trait makes the CLI values `git-hub`
 // and `git-lab`
 impl clap::ValueEnum for RepositoryProvider {
@@ -1,7 +1,11 @@
 use console::style;
+use std::path::Path;
 
-use crate::api_client::CodSpeedAPIClient;
-use crate::cli::run::helpers::benchmark_display::build_benchmark_table;
+use crate::api_client::{CodSpeedAPIClient, FetchLocalRunBenchmarkResult};
+use crate::cli::compare::profile_results::load_walltime_results;
+use crate::cli::run::helpers::benchmark_display::{
+    build_benchmark_table, build_resource_usage_table,
+};
 use crate::prelude::*;
 use crate::upload::{UploadResult, poll_run_report};
 
@@ -9,6 +13,7 @@ use crate::upload::{UploadResult, poll_run_report};
 pub async fn poll_results(
     api_client: &CodSpeedAPIClient,
     upload_result: &UploadResult,
+    profile_folder: &Path,
     output_json: bool,
 ) -> Result<()> {
     let response = poll_run_report(api_client, upload_result).await?;
@@ -35,12 +40,7 @@ pub async fn poll_results(
     }
 
     if output_json {
-        // TODO: Refactor `log_json` to avoid having to format the json manually
-        // We could make use of structured logging for this https://docs.rs/log/latest/log/#structured-logging
-        log_json!(format!(
-            "{{\"event\": \"run_finished\", \"run_id\": \"{}\"}}",
-            upload_result.run_id
-        ));
+        log_run_finished_json(upload_result);
     }
 
     if !response.run.results.is_empty() {
@@ -49,14 +49,10 @@ pub async fn poll_results(
 
         let table = build_benchmark_table(&response.run.results);
         info!("\n{table}");
+        log_resource_usage_table(profile_folder);
 
         if output_json {
-            for result in response.run.results {
-                log_json!(format!(
-                    "{{\"event\": \"benchmark_ran\", \"name\": \"{}\", \"time\": \"{}\"}}",
-                    result.benchmark.name, result.value
-                ));
-            }
+            log_benchmarks_ran_json(&response.run.results);
         }
 
         info!(
@@ -67,3 +63,36 @@ pub async fn poll_results(
 
     Ok(())
 }
+
+/// Log the `run_finished` event of `--message-format json`
+pub(crate) fn log_run_finished_json(upload_result: &UploadResult) {
+    // TODO: Refactor `log_json` to avoid having to format the json manually
+    // We could make use of structured logging for this https://docs.rs/log/latest/log/#structured-logging
+    log_json!(format!(
+        "{{\"event\": \"run_finished\", \"run_id\": \"{}\"}}",
+        upload_result.run_id
+    ));
+}
+
+/// Log the `benchmark_ran` events of `--message-format json`
+pub(crate) fn log_benchmarks_ran_json(results: &[FetchLocalRunBenchmarkResult]) {
+    for result in results {
+        log_json!(format!(
+            "{{\"event\": \"benchmark_ran\", \"name\": \"{}\", \"time\": \"{}\"}}",
+            result.benchmark.name, result.value
+        ));
+    }
+}
+
+/// Log the resource usage of the walltime benchmarks of the profile folder, which is only
+/// collected locally and not part of the run report
+pub(crate) fn log_resource_usage_table(profile_folder: &Path) {
+    match load_walltime_results(profile_folder) {
+        Ok(entries) => {
+            if let Some(table) = build_resource_usage_table(&entries) {
+                info!("\n{table}");
+            }
+        }
+        Err(e) => debug!("Failed to load the local walltime results: {e:#}"),
+    }
+}
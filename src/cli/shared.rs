@@ -4,6 +4,8 @@ use crate::run_environment::interfaces::RepositoryProvider;
 use crate::runner_mode::{RunnerMode, load_shell_session_mode};
 use clap::Args;
 use clap::ValueEnum;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 pub(crate) fn show_banner() {
@@ -96,7 +98,7 @@ impl ExecAndRunSharedArgs {
     /// Resolves the runner mode from CLI argument, shell session, or returns an error.
     ///
     /// Priority:
-    /// 1. CLI argument (--mode or -m)
+    /// 1. CLI argument (--mode or -m), or `mode` from the project config once merged
     /// 2. Shell session mode (set via `codspeed use <mode>`)
     /// 3. Error if neither is available
     pub fn resolve_mode(&self) -> Result<RunnerMode> {
@@ -115,10 +117,12 @@ impl ExecAndRunSharedArgs {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, ValueEnum, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, ValueEnum, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum UnwindingMode {
     /// Use the frame pointer for unwinding. Requires the binary to be compiled with frame pointers enabled.
     #[clap(name = "fp")]
+    #[serde(rename = "fp")]
     FramePointer,
 
     /// Use DWARF unwinding. This does not require any special compilation flags and is enabled by default.
@@ -129,15 +133,29 @@ pub enum UnwindingMode {
 #[derive(Args, Debug, Clone)]
 pub struct PerfRunArgs {
     /// Enable the linux perf profiler to collect granular performance data.
-    /// This is only supported on Linux.
-    #[arg(long, env = "CODSPEED_PERF_ENABLED", default_value_t = true)]
-    pub enable_perf: bool,
+    /// This is only supported on Linux. Enabled by default, use `--enable-perf=false` to disable it.
+    #[arg(
+        long,
+        env = "CODSPEED_PERF_ENABLED",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    pub enable_perf: Option<bool>,
 
     /// The unwinding mode that should be used with perf to collect the call stack.
     #[arg(long, env = "CODSPEED_PERF_UNWINDING_MODE")]
     pub perf_unwinding_mode: Option<UnwindingMode>,
 }
 
+impl PerfRunArgs {
+    /// Whether perf is enabled, when not disabled from the CLI, the environment or the config
+    pub fn is_perf_enabled(&self) -> bool {
+        self.enable_perf.unwrap_or(true)
+    }
+}
+
 /// Parser for go-runner version that validates semver format
 fn parse_version(s: &str) -> Result<semver::Version, String> {
     semver::Version::parse(s).map_err(|e| format!("Invalid semantic version: {e}"))
@@ -0,0 +1,173 @@
+use crate::api_client::CodSpeedAPIClient;
+use crate::config::CodSpeedConfig;
+use crate::executor;
+use crate::executor::{Config, ExecutionContext};
+use crate::prelude::*;
+use crate::run_environment::interfaces::RepositoryProvider;
+use crate::runner_mode::RunnerMode;
+use crate::upload::UploadResult;
+use clap::Args;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct UploadArgs {
+    /// Profile folder containing the results of a previous run, for example produced in a sandbox
+    /// environment with no internet access
+    #[arg(long)]
+    pub profile_folder: PathBuf,
+
+    /// The mode the benchmarks of the profile folder were run in
+    #[arg(short, long, value_enum, env = "CODSPEED_RUNNER_MODE")]
+    pub mode: RunnerMode,
+
+    /// The upload URL to use for uploading the results, useful for on-premises installations
+    #[arg(long, env = "CODSPEED_UPLOAD_URL")]
+    pub upload_url: Option<String>,
+
+    /// The token to use for uploading the results,
+    ///
+    /// It can be either a CodSpeed token retrieved from the repository setting
+    /// or an OIDC token issued by the identity provider.
+    #[arg(long, env = "CODSPEED_TOKEN")]
+    pub token: Option<String>,
+
+    /// The repository the benchmark is associated with, under the format `owner/repo`.
+    #[arg(short, long, env = "CODSPEED_REPOSITORY")]
+    pub repository: Option<String>,
+
+    /// The repository provider to use in case --repository is used. Defaults to github
+    #[arg(
+        long,
+        env = "CODSPEED_PROVIDER",
+        requires = "repository",
+        ignore_case = true
+    )]
+    pub provider: Option<RepositoryProvider>,
+
+    /// Allow uploading a profile folder without any benchmarks instead of failing
+    #[arg(long, default_value = "false", hide = true)]
+    pub allow_empty: bool,
+}
+
+pub async fn run(
+    args: UploadArgs,
+    api_client: &CodSpeedAPIClient,
+    codspeed_config: &CodSpeedConfig,
+) -> Result<()> {
+    let config = Config::try_from(args)?;
+
+    let mut execution_context =
+        executor::ExecutionContext::new(config, codspeed_config, api_client).await?;
+
+    if !execution_context.is_local() {
+        super::show_banner();
+    }
+    debug!("config: {:#?}", execution_context.config);
+
+    let profile_folder = execution_context.profile_folder.clone();
+    let poll_results_fn = async |upload_result: &UploadResult| {
+        super::run::poll_results::poll_results(api_client, upload_result, &profile_folder, false)
+            .await
+    };
+
+    upload_profile_folder(&mut execution_context, poll_results_fn).await
+}
+
+/// Uploads the profile folder of the execution context through the upload step of
+/// [`executor::execute_benchmarks`], the config skipping the setup and the run
+async fn upload_profile_folder<F>(
+    execution_context: &mut ExecutionContext,
+    poll_results: F,
+) -> Result<()>
+where
+    F: AsyncFn(&UploadResult) -> Result<()>,
+{
+    executor::validate_profile_folder(
+        &execution_context.config.mode,
+        &execution_context.profile_folder,
+        execution_context.config.allow_empty,
+    )?;
+
+    let executor = executor::get_executor_from_mode(&execution_context.config.mode);
+    executor::execute_benchmarks(executor.as_ref(), execution_context, None, poll_results).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::run::logger::Logger;
+    use crate::run_environment::{
+        RunEnvironment, RunEnvironmentMetadata, RunEnvironmentProvider, RunPart,
+    };
+    use crate::system::SystemInfo;
+    use async_trait::async_trait;
+    use simplelog::SharedLogger;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use tempfile::TempDir;
+
+    /// CI provider recording the OIDC token requests, failing them to stop before the upload
+    struct OidcProvider {
+        oidc_token_requested: Rc<Cell<bool>>,
+    }
+
+    #[async_trait(?Send)]
+    impl RunEnvironmentProvider for OidcProvider {
+        fn get_logger(&self) -> Box<dyn SharedLogger> {
+            simplelog::SimpleLogger::new(log::LevelFilter::Off, simplelog::Config::default())
+        }
+
+        fn get_repository_provider(&self) -> RepositoryProvider {
+            RepositoryProvider::GitHub
+        }
+
+        fn get_run_environment(&self) -> RunEnvironment {
+            RunEnvironment::GithubActions
+        }
+
+        fn get_run_environment_metadata(&self) -> Result<RunEnvironmentMetadata> {
+            bail!("No run environment metadata")
+        }
+
+        fn get_run_provider_run_part(&self) -> Option<RunPart> {
+            None
+        }
+
+        async fn set_oidc_token(&self, _config: &mut Config) -> Result<()> {
+            self.oidc_token_requested.set(true);
+            bail!("No OIDC token")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_requests_oidc_token_outside_local_environment() {
+        let profile_folder = TempDir::new().unwrap();
+        let config = Config::try_from(UploadArgs {
+            profile_folder: profile_folder.path().to_path_buf(),
+            mode: RunnerMode::Simulation,
+            upload_url: None,
+            token: None,
+            repository: None,
+            provider: None,
+            allow_empty: false,
+        })
+        .unwrap();
+        let oidc_token_requested = Rc::new(Cell::new(false));
+        let provider = OidcProvider {
+            oidc_token_requested: oidc_token_requested.clone(),
+        };
+        let mut execution_context = ExecutionContext {
+            config,
+            profile_folder: profile_folder.path().to_path_buf(),
+            system_info: SystemInfo::test(),
+            logger: Logger::new(&provider).unwrap(),
+            provider: Box::new(provider),
+        };
+
+        let result =
+            upload_profile_folder(&mut execution_context, async |_: &UploadResult| Ok(())).await;
+
+        assert!(result.unwrap_err().to_string().contains("No OIDC token"));
+        assert!(oidc_token_requested.get());
+    }
+}
@@ -27,7 +27,7 @@ nest! {
 ///
 /// If config_name is None, returns ~/.config/codspeed/config.yaml (default)
 /// If config_name is Some, returns ~/.config/codspeed/{config_name}.yaml
-fn get_configuration_file_path(config_name: Option<&str>) -> PathBuf {
+pub fn get_configuration_file_path(config_name: Option<&str>) -> PathBuf {
     let config_dir = env::var("XDG_CONFIG_HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| {
@@ -5,6 +5,7 @@ use crate::instruments::Instruments;
 use crate::prelude::*;
 use crate::run_environment::RepositoryProvider;
 use crate::runner_mode::RunnerMode;
+use runner_shared::walltime_results::ProcessSettings;
 use semver::Version;
 use std::path::PathBuf;
 use url::Url;
@@ -38,6 +39,9 @@ pub struct Config {
     pub allow_empty: bool,
     /// The version of go-runner to install (if None, installs latest)
     pub go_runner_version: Option<Version>,
+    /// Scheduling and address space settings of the benchmark process in walltime mode, left to
+    /// exec-harness when it runs the benchmarks
+    pub process_settings: ProcessSettings,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -90,6 +94,7 @@ impl Config {
             skip_setup: false,
             allow_empty: false,
             go_runner_version: None,
+            process_settings: ProcessSettings::default(),
         }
     }
 }
@@ -100,6 +105,7 @@ impl TryFrom<RunArgs> for Config {
     type Error = Error;
     fn try_from(args: RunArgs) -> Result<Self> {
         let instruments = Instruments::try_from(&args)?;
+        let process_settings = args.process_settings.to_settings()?;
         let mode = args.shared.resolve_mode()?;
         let raw_upload_url = args
             .shared
@@ -120,7 +126,7 @@ impl TryFrom<RunArgs> for Config {
             mode,
             instruments,
             perf_unwinding_mode: args.shared.perf_run_args.perf_unwinding_mode,
-            enable_perf: args.shared.perf_run_args.enable_perf,
+            enable_perf: args.shared.perf_run_args.is_perf_enabled(),
             command: args.command.join(" "),
             profile_folder: args.shared.profile_folder,
             skip_upload: args.shared.skip_upload,
@@ -128,6 +134,7 @@ impl TryFrom<RunArgs> for Config {
             skip_setup: args.shared.skip_setup,
             allow_empty: args.shared.allow_empty,
             go_runner_version: args.shared.go_runner_version,
+            process_settings,
         })
     }
 }
@@ -158,7 +165,7 @@ impl Config {
             mode,
             instruments: Instruments { mongodb: None }, // exec doesn't support MongoDB
             perf_unwinding_mode: args.shared.perf_run_args.perf_unwinding_mode,
-            enable_perf: args.shared.perf_run_args.enable_perf,
+            enable_perf: args.shared.perf_run_args.is_perf_enabled(),
             command,
             profile_folder: args.shared.profile_folder,
             skip_upload: args.shared.skip_upload,
@@ -166,6 +173,8 @@ impl Config {
             skip_setup: args.shared.skip_setup,
             allow_empty: args.shared.allow_empty,
             go_runner_version: args.shared.go_runner_version,
+            // Applied to each execution of the benchmarks by exec-harness
+            process_settings: ProcessSettings::default(),
         })
     }
 }
@@ -173,11 +182,49 @@ impl Config {
 impl TryFrom<crate::cli::exec::ExecArgs> for Config {
     type Error = Error;
     fn try_from(args: crate::cli::exec::ExecArgs) -> Result<Self> {
-        let wrapped_command = wrap_with_exec_harness(&args.walltime_args, &args.command);
+        let wrapped_command = wrap_with_exec_harness(
+            &args.walltime_args,
+            &args.filter_args,
+            &args.scheduling_args,
+            &args.assertions,
+            &args.stdio,
+            &args.command,
+        );
         Self::try_from_with_command(args, wrapped_command)
     }
 }
 
+impl TryFrom<crate::cli::upload::UploadArgs> for Config {
+    type Error = Error;
+    fn try_from(args: crate::cli::upload::UploadArgs) -> Result<Self> {
+        let raw_upload_url = args.upload_url.unwrap_or_else(|| DEFAULT_UPLOAD_URL.into());
+        let upload_url = Url::parse(&raw_upload_url)
+            .map_err(|e| anyhow!("Invalid upload URL: {raw_upload_url}, {e}"))?;
+
+        Ok(Self {
+            upload_url,
+            token: args.token,
+            repository_override: args
+                .repository
+                .map(|repo| RepositoryOverride::from_arg(repo, args.provider))
+                .transpose()?,
+            working_directory: None,
+            mode: args.mode,
+            instruments: Instruments { mongodb: None },
+            perf_unwinding_mode: None,
+            enable_perf: false,
+            command: String::new(),
+            profile_folder: Some(args.profile_folder),
+            skip_upload: false,
+            skip_run: true,
+            skip_setup: true,
+            allow_empty: args.allow_empty,
+            go_runner_version: None,
+            process_settings: ProcessSettings::default(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::cli::PerfRunArgs;
@@ -202,13 +249,15 @@ mod tests {
                 allow_empty: false,
                 go_runner_version: None,
                 perf_run_args: PerfRunArgs {
-                    enable_perf: false,
+                    enable_perf: Some(false),
                     perf_unwinding_mode: None,
                 },
             },
             instruments: vec![],
             mongo_uri_env_name: None,
             message_format: None,
+            filter_args: Default::default(),
+            process_settings: Default::default(),
             command: vec!["cargo".into(), "codspeed".into(), "bench".into()],
         })
         .unwrap();
@@ -241,13 +290,15 @@ mod tests {
                 allow_empty: true,
                 go_runner_version: None,
                 perf_run_args: PerfRunArgs {
-                    enable_perf: false,
+                    enable_perf: Some(false),
                     perf_unwinding_mode: Some(UnwindingMode::FramePointer),
                 },
             },
             instruments: vec!["mongodb".into()],
             mongo_uri_env_name: Some("MONGODB_URI".into()),
             message_format: Some(crate::cli::run::MessageFormat::Json),
+            filter_args: Default::default(),
+            process_settings: Default::default(),
             command: vec!["cargo".into(), "codspeed".into(), "bench".into()],
         })
         .unwrap();
@@ -307,6 +358,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_try_from_upload_args() {
+        let config = Config::try_from(crate::cli::upload::UploadArgs {
+            profile_folder: "./codspeed.out".into(),
+            mode: RunnerMode::Walltime,
+            upload_url: None,
+            token: Some("token".into()),
+            repository: Some("owner/repo".into()),
+            provider: None,
+            allow_empty: false,
+        })
+        .unwrap();
+
+        assert_eq!(config.upload_url, Url::parse(DEFAULT_UPLOAD_URL).unwrap());
+        assert_eq!(config.token, Some("token".into()));
+        assert_eq!(config.mode, RunnerMode::Walltime);
+        assert_eq!(config.profile_folder, Some("./codspeed.out".into()));
+        assert_eq!(
+            config.repository_override.map(|r| r.repository_provider),
+            Some(RepositoryProvider::GitHub)
+        );
+        assert!(config.skip_run);
+        assert!(config.skip_setup);
+        assert!(!config.skip_upload);
+        assert_eq!(config.command, "");
+    }
+
     #[test]
     fn test_try_from_exec_args_default_url() {
         let exec_args = crate::cli::exec::ExecArgs {
@@ -324,11 +402,15 @@ mod tests {
                 allow_empty: false,
                 go_runner_version: None,
                 perf_run_args: PerfRunArgs {
-                    enable_perf: false,
+                    enable_perf: Some(false),
                     perf_unwinding_mode: None,
                 },
             },
             walltime_args: Default::default(),
+            filter_args: Default::default(),
+            scheduling_args: Default::default(),
+            assertions: Default::default(),
+            stdio: Default::default(),
             name: None,
             command: vec!["my-binary".into(), "arg1".into(), "arg2".into()],
         };
@@ -341,4 +423,28 @@ mod tests {
         );
         assert_eq!(config.command, "exec-harness my-binary arg1 arg2");
     }
+
+    #[test]
+    fn test_try_from_exec_args_forwards_filters() {
+        let exec_args = crate::cli::exec::ExecArgs {
+            shared: RunArgs::test().shared,
+            walltime_args: Default::default(),
+            filter_args: exec_harness::filter::BenchmarkFilterArgs {
+                bench: vec!["parse_*".into()],
+                exclude: vec!["re:slow$".into()],
+            },
+            scheduling_args: Default::default(),
+            assertions: Default::default(),
+            stdio: Default::default(),
+            name: None,
+            command: vec!["-".into()],
+        };
+
+        let config = Config::try_from(exec_args).unwrap();
+
+        assert_eq!(
+            config.command,
+            "exec-harness --bench 'parse_*' --exclude 're:slow$' -"
+        );
+    }
 }
@@ -0,0 +1,78 @@
+use crate::executor::helpers::run_with_sudo::{
+    is_passwordless_sudo_available, is_root_user, is_sudo_available,
+};
+use serde::Serialize;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Result of a single environment check performed by `codspeed doctor`
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub name: String,
+    pub status: DiagnosticStatus,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn pass(name: impl Display, message: impl Display) -> Self {
+        Self::new(name, DiagnosticStatus::Pass, message)
+    }
+
+    pub fn warn(name: impl Display, message: impl Display) -> Self {
+        Self::new(name, DiagnosticStatus::Warn, message)
+    }
+
+    pub fn fail(name: impl Display, message: impl Display) -> Self {
+        Self::new(name, DiagnosticStatus::Fail, message)
+    }
+
+    fn new(name: impl Display, status: DiagnosticStatus, message: impl Display) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Checks that the executors needing elevated privileges will be able to acquire them
+pub fn diagnose_sudo() -> Diagnostic {
+    const NAME: &str = "sudo";
+
+    if is_root_user() {
+        Diagnostic::pass(NAME, "running as root")
+    } else if !is_sudo_available() {
+        Diagnostic::fail(
+            NAME,
+            "sudo is not installed and the runner is not run as root",
+        )
+    } else if is_passwordless_sudo_available() {
+        Diagnostic::pass(NAME, "sudo is available without password")
+    } else {
+        Diagnostic::warn(
+            NAME,
+            "sudo requires a password, the runner will prompt for it in interactive sessions only",
+        )
+    }
+}
+
+/// Checks that a command is available in the PATH
+pub fn diagnose_command_available(command: &str, missing_status: DiagnosticStatus) -> Diagnostic {
+    let is_available = std::process::Command::new("which")
+        .arg(command)
+        .output()
+        .is_ok_and(|output| output.status.success());
+
+    if is_available {
+        Diagnostic::pass(command, "found in PATH")
+    } else {
+        Diagnostic::new(command, missing_status, "not found in PATH")
+    }
+}
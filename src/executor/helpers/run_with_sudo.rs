@@ -6,14 +6,14 @@ use std::{
     process::{Command, Stdio},
 };
 
-fn is_root_user() -> bool {
+pub fn is_root_user() -> bool {
     #[cfg(unix)]
     return nix::unistd::Uid::current().is_root();
     #[cfg(not(unix))]
     return false;
 }
 
-fn is_sudo_available() -> bool {
+pub fn is_sudo_available() -> bool {
     Command::new("sudo")
         .arg("--version")
         .stdout(Stdio::null())
@@ -23,17 +23,22 @@ fn is_sudo_available() -> bool {
         .unwrap_or(false)
 }
 
+/// Whether sudo can be used without prompting the user for their password
+pub fn is_passwordless_sudo_available() -> bool {
+    Command::new("sudo")
+        .arg("--non-interactive") // Fail if password is required
+        .arg("true")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
 /// Validate sudo access, prompting the user for their password if necessary
 fn validate_sudo_access() -> Result<()> {
-    let needs_password = IsTerminal::is_terminal(&std::io::stdout())
-        && Command::new("sudo")
-            .arg("--non-interactive") // Fail if password is required
-            .arg("true")
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .map(|status| !status.success())
-            .unwrap_or(true);
+    let needs_password =
+        IsTerminal::is_terminal(&std::io::stdout()) && !is_passwordless_sudo_available();
 
     if needs_password {
         suspend_progress_bar(|| {
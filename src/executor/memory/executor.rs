@@ -1,11 +1,15 @@
 use crate::binary_installer::ensure_binary_installed;
 use crate::executor::ExecutorName;
+use crate::executor::diagnostics::{
+    Diagnostic, DiagnosticStatus, diagnose_command_available, diagnose_sudo,
+};
 use crate::executor::helpers::command::CommandBuilder;
 use crate::executor::helpers::env::get_base_injected_env;
 use crate::executor::helpers::get_bench_command::get_bench_command;
 use crate::executor::helpers::run_command_with_log_pipe::run_command_with_log_pipe_and_callback;
 use crate::executor::helpers::run_with_env::wrap_with_env;
 use crate::executor::helpers::run_with_sudo::wrap_with_sudo;
+use crate::executor::helpers::run_with_sudo::{is_passwordless_sudo_available, is_root_user};
 use crate::executor::shared::fifo::RunnerFifo;
 use crate::executor::{ExecutionContext, Executor};
 use crate::instruments::mongo_tracer::MongoTracer;
@@ -31,7 +35,71 @@ const MEMTRACK_CODSPEED_VERSION: &str = "1.2.2";
 
 pub struct MemoryExecutor;
 
+/// Validates that the memory results of the profile folder contain at least one benchmark.
+/// When `allow_empty` is true, empty benchmark results are allowed.
+pub fn validate_memory_results(profile_folder: &Path, allow_empty: bool) -> Result<()> {
+    let results_dir = profile_folder.join("results");
+    let has_benchmarks = std::fs::read_dir(&results_dir)
+        .with_context(|| format!("Failed to read the results directory: {results_dir:?}"))?
+        .filter_map(Result::ok)
+        // Filter out non-ExecutionTimestamps files:
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .contains(ExecutionTimestamps::name())
+        })
+        .filter_map(|entry| {
+            let file = std::fs::File::open(entry.path()).ok()?;
+            ExecutionTimestamps::decode_from_reader(file).ok()
+        })
+        .any(|artifact| !artifact.uri_by_ts.is_empty());
+
+    if !has_benchmarks {
+        if !allow_empty {
+            bail!("No memory results found in profile folder: {results_dir:?}.");
+        } else {
+            info!("No memory results found in profile folder: {results_dir:?}.");
+        }
+    }
+
+    Ok(())
+}
+
 impl MemoryExecutor {
+    /// Tracks a no-op command, which requires memtrack to load and attach its BPF programs
+    fn diagnose_bpf() -> Diagnostic {
+        const NAME: &str = "bpf";
+
+        if !is_root_user() && !is_passwordless_sudo_available() {
+            return Diagnostic::warn(NAME, "skipped, loading BPF programs requires sudo");
+        }
+
+        let output_dir = match tempfile::tempdir() {
+            Ok(output_dir) => output_dir,
+            Err(e) => return Diagnostic::fail(NAME, format!("failed to create a temp dir: {e}")),
+        };
+        let mut cmd_builder = CommandBuilder::new(MEMTRACK_COMMAND);
+        cmd_builder.args(["track", "true", "--output"]);
+        cmd_builder.arg(output_dir.path());
+        let output =
+            wrap_with_sudo(cmd_builder).and_then(|cmd_builder| Ok(cmd_builder.build().output()?));
+
+        match output {
+            Ok(output) if output.status.success() => {
+                Diagnostic::pass(NAME, "memtrack is able to load its BPF programs")
+            }
+            Ok(output) => Diagnostic::fail(
+                NAME,
+                format!(
+                    "memtrack failed to load its BPF programs: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            ),
+            Err(e) => Diagnostic::fail(NAME, format!("failed to run memtrack: {e}")),
+        }
+    }
+
     fn build_memtrack_command(
         execution_context: &ExecutionContext,
     ) -> Result<(MemtrackIpcServer, CommandBuilder, NamedTempFile)> {
@@ -92,6 +160,17 @@ impl Executor for MemoryExecutor {
         .await
     }
 
+    fn diagnose(&self, _system_info: &SystemInfo) -> Vec<Diagnostic> {
+        let memtrack = diagnose_command_available(MEMTRACK_COMMAND, DiagnosticStatus::Warn);
+        let is_memtrack_installed = memtrack.status == DiagnosticStatus::Pass;
+        let mut diagnostics = vec![diagnose_sudo(), memtrack];
+
+        if is_memtrack_installed {
+            diagnostics.push(Self::diagnose_bpf());
+        }
+        diagnostics
+    }
+
     async fn run(
         &self,
         execution_context: &ExecutionContext,
@@ -128,31 +207,10 @@ impl Executor for MemoryExecutor {
     }
 
     async fn teardown(&self, execution_context: &ExecutionContext) -> Result<()> {
-        let results_dir = execution_context.profile_folder.join("results");
-        let has_benchmarks = std::fs::read_dir(&results_dir)?
-            .filter_map(Result::ok)
-            // Filter out non-ExecutionTimestamps files:
-            .filter(|entry| {
-                entry
-                    .file_name()
-                    .to_string_lossy()
-                    .contains(ExecutionTimestamps::name())
-            })
-            .filter_map(|entry| {
-                let file = std::fs::File::open(entry.path()).ok()?;
-                ExecutionTimestamps::decode_from_reader(file).ok()
-            })
-            .any(|artifact| !artifact.uri_by_ts.is_empty());
-
-        if !has_benchmarks {
-            if !execution_context.config.allow_empty {
-                bail!("No memory results found in profile folder: {results_dir:?}.");
-            } else {
-                info!("No memory results found in profile folder: {results_dir:?}.");
-            }
-        }
-
-        Ok(())
+        validate_memory_results(
+            &execution_context.profile_folder,
+            execution_context.config.allow_empty,
+        )
     }
 }
 
@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 pub mod config;
+pub mod diagnostics;
 mod execution_context;
 mod helpers;
 mod interfaces;
@@ -18,6 +19,7 @@ use crate::system::SystemInfo;
 use crate::upload::UploadResult;
 use async_trait::async_trait;
 pub use config::Config;
+pub use diagnostics::{Diagnostic, DiagnosticStatus};
 pub use execution_context::ExecutionContext;
 pub use helpers::profile_folder::create_profile_folder;
 pub use interfaces::ExecutorName;
@@ -58,6 +60,31 @@ pub fn get_all_executors() -> Vec<Box<dyn Executor>> {
     ]
 }
 
+/// Validates the content of a profile folder produced by a previous run of the given mode, before
+/// uploading it
+pub fn validate_profile_folder(
+    mode: &RunnerMode,
+    profile_folder: &Path,
+    allow_empty: bool,
+) -> Result<()> {
+    if !profile_folder.is_dir() {
+        bail!("Profile folder not found: {profile_folder:?}");
+    }
+
+    match mode {
+        // Checking the valgrind results would require parsing its output files, this is left to
+        // the backend as it is done for regular runs
+        #[allow(deprecated)]
+        RunnerMode::Instrumentation | RunnerMode::Simulation => Ok(()),
+        RunnerMode::Walltime => {
+            wall_time::helpers::validate_walltime_results(profile_folder, allow_empty)
+        }
+        RunnerMode::Memory => {
+            memory::executor::validate_memory_results(profile_folder, allow_empty)
+        }
+    }
+}
+
 #[async_trait(?Send)]
 pub trait Executor {
     fn name(&self) -> ExecutorName;
@@ -70,6 +97,9 @@ pub trait Executor {
         Ok(())
     }
 
+    /// Checks whether the current environment is able to run this executor
+    fn diagnose(&self, system_info: &SystemInfo) -> Vec<Diagnostic>;
+
     /// Runs the executor
     async fn run(
         &self,
@@ -365,7 +365,10 @@ fi
     #[test_log::test(tokio::test)]
     async fn test_exec_harness(#[case] cmd: &str) {
         use crate::cli::exec::wrap_with_exec_harness;
-        use exec_harness::walltime::WalltimeExecutionArgs;
+        use exec_harness::assertions::BenchmarkAssertions;
+        use exec_harness::filter::BenchmarkFilterArgs;
+        use exec_harness::stdio::BenchmarkStdio;
+        use exec_harness::walltime::{SchedulingArgs, WalltimeExecutionArgs};
 
         let (_permit, executor) = get_walltime_executor().await;
 
@@ -375,10 +378,21 @@ fi
             min_time: None,
             max_rounds: Some(3),
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         };
 
         let cmd = cmd.split(" ").map(|s| s.to_owned()).collect::<Vec<_>>();
-        let wrapped_command = wrap_with_exec_harness(&walltime_args, &cmd);
+        let wrapped_command = wrap_with_exec_harness(
+            &walltime_args,
+            &BenchmarkFilterArgs::default(),
+            &SchedulingArgs::default(),
+            &BenchmarkAssertions::default(),
+            &BenchmarkStdio::default(),
+            &cmd,
+        );
 
         // Unset GITHUB_ACTIONS to force LocalProvider which supports repository_override
         temp_env::async_with_vars(&[("GITHUB_ACTIONS", None::<&str>)], async {
@@ -2,12 +2,13 @@ use async_trait::async_trait;
 use std::path::Path;
 
 use crate::executor::Executor;
+use crate::executor::diagnostics::Diagnostic;
 use crate::executor::{ExecutionContext, ExecutorName};
 use crate::instruments::mongo_tracer::MongoTracer;
 use crate::prelude::*;
 use crate::system::SystemInfo;
 
-use super::setup::install_valgrind;
+use super::setup::{diagnose_valgrind, install_valgrind};
 use super::{helpers::perf_maps::harvest_perf_maps, helpers::venv_compat, measure};
 
 pub struct ValgrindExecutor;
@@ -29,6 +30,10 @@ impl Executor for ValgrindExecutor {
         Ok(())
     }
 
+    fn diagnose(&self, system_info: &SystemInfo) -> Vec<Diagnostic> {
+        vec![diagnose_valgrind(system_info)]
+    }
+
     async fn run(
         &self,
         execution_context: &ExecutionContext,
@@ -1,4 +1,5 @@
 use crate::cli::run::helpers::download_file;
+use crate::executor::diagnostics::Diagnostic;
 use crate::executor::helpers::apt;
 use crate::prelude::*;
 use crate::system::SystemInfo;
@@ -113,6 +114,43 @@ fn is_valgrind_installed() -> bool {
     true
 }
 
+/// Reports whether a compatible valgrind is installed or can be installed by `codspeed setup`
+pub fn diagnose_valgrind(system_info: &SystemInfo) -> Diagnostic {
+    const NAME: &str = "valgrind";
+
+    let Ok(version_output) = Command::new("valgrind").arg("--version").output() else {
+        return match get_codspeed_valgrind_filename(system_info) {
+            Ok(_) => Diagnostic::warn(NAME, "not installed, it will be installed on the next run"),
+            Err(_) => Diagnostic::fail(
+                NAME,
+                "not installed and no CodSpeed build of valgrind is available for this system",
+            ),
+        };
+    };
+    if !version_output.status.success() {
+        return Diagnostic::fail(NAME, "failed to get the installed version");
+    }
+
+    let version = String::from_utf8_lossy(&version_output.stdout);
+    let version = version.trim();
+    let expected_version = VALGRIND_CODSPEED_VERSION_STRING.as_str();
+    match parse_valgrind_codspeed_version(version) {
+        None => Diagnostic::fail(
+            NAME,
+            format!("{version} is not a CodSpeed version, expecting {expected_version}"),
+        ),
+        Some(installed) if installed < VALGRIND_CODSPEED_VERSION => Diagnostic::fail(
+            NAME,
+            format!("{version} is too old, expecting {expected_version} or higher"),
+        ),
+        Some(installed) if installed > VALGRIND_CODSPEED_VERSION => Diagnostic::warn(
+            NAME,
+            format!("{version} is experimental, the recommended version is {expected_version}"),
+        ),
+        Some(_) => Diagnostic::pass(NAME, version),
+    }
+}
+
 pub async fn install_valgrind(
     system_info: &SystemInfo,
     setup_cache_dir: Option<&Path>,
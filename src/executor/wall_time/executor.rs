@@ -2,6 +2,9 @@ use super::helpers::validate_walltime_results;
 use super::perf::PerfRunner;
 use crate::executor::Config;
 use crate::executor::Executor;
+use crate::executor::diagnostics::{
+    Diagnostic, DiagnosticStatus, diagnose_command_available, diagnose_sudo,
+};
 use crate::executor::helpers::command::CommandBuilder;
 use crate::executor::helpers::env::{get_base_injected_env, is_codspeed_debug_enabled};
 use crate::executor::helpers::get_bench_command::get_bench_command;
@@ -16,6 +19,9 @@ use crate::prelude::*;
 use crate::runner_mode::RunnerMode;
 use crate::system::SystemInfo;
 use async_trait::async_trait;
+use itertools::Itertools;
+use runner_shared::walltime_results::ProcessSettings;
+use std::env::consts::ARCH;
 use std::fs::canonicalize;
 use std::io::Write;
 use std::path::Path;
@@ -137,11 +143,30 @@ impl WallTimeExecutor {
         cmd_builder.args(["--"]);
 
         bench_cmd.wrap_with(cmd_builder);
+        wrap_with_process_settings(&mut bench_cmd, &config.process_settings);
 
         Ok((env_file, script_file, bench_cmd))
     }
 }
 
+/// Apply the process settings to the benchmark process by wrapping `systemd-run`, whose settings
+/// are inherited by the command it executes. The wrappers run before `systemd-run` drops the
+/// privileges, which are required to raise the priority.
+fn wrap_with_process_settings(cmd_builder: &mut CommandBuilder, settings: &ProcessSettings) {
+    if settings.aslr_disabled {
+        cmd_builder.wrap("setarch", [ARCH, "-R"]);
+    }
+    if let Some(nice) = settings.nice {
+        cmd_builder.wrap("nice", ["-n".to_string(), nice.to_string()]);
+    }
+    if let Some(priority) = settings.sched_fifo_priority {
+        cmd_builder.wrap("chrt", ["--fifo".to_string(), priority.to_string()]);
+    }
+    if let Some(cpus) = &settings.cpu_affinity {
+        cmd_builder.wrap("taskset", ["--cpu-list".to_string(), cpus.iter().join(",")]);
+    }
+}
+
 #[async_trait(?Send)]
 impl Executor for WallTimeExecutor {
     fn name(&self) -> ExecutorName {
@@ -156,6 +181,17 @@ impl Executor for WallTimeExecutor {
         Ok(())
     }
 
+    fn diagnose(&self, _system_info: &SystemInfo) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![
+            diagnose_sudo(),
+            diagnose_command_available("systemd-run", DiagnosticStatus::Fail),
+        ];
+        if self.perf.is_some() {
+            diagnostics.extend(PerfRunner::diagnose_environment());
+        }
+        diagnostics
+    }
+
     async fn run(
         &self,
         execution_context: &ExecutionContext,
@@ -221,6 +257,33 @@ mod tests {
         os::unix::fs::PermissionsExt,
     };
 
+    #[test]
+    fn test_wrap_with_process_settings() {
+        let mut cmd_builder = CommandBuilder::new("systemd-run");
+        cmd_builder.args(["--scope", "--", "bash", "bench.sh"]);
+        wrap_with_process_settings(&mut cmd_builder, &ProcessSettings::default());
+        assert_eq!(
+            cmd_builder.as_command_line(),
+            "systemd-run --scope -- bash bench.sh"
+        );
+
+        wrap_with_process_settings(
+            &mut cmd_builder,
+            &ProcessSettings {
+                cpu_affinity: Some(vec![0, 2, 3]),
+                nice: None,
+                sched_fifo_priority: Some(50),
+                aslr_disabled: true,
+            },
+        );
+        assert_eq!(
+            cmd_builder.as_command_line(),
+            format!(
+                "taskset --cpu-list 0,2,3 chrt --fifo 50 setarch {ARCH} -R systemd-run --scope -- bash bench.sh"
+            )
+        );
+    }
+
     #[test]
     fn test_env_guard_no_crash() {
         fn create_run_script(content: &str) -> anyhow::Result<NamedTempFile> {
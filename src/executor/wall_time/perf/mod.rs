@@ -2,6 +2,7 @@
 
 use crate::cli::UnwindingMode;
 use crate::executor::Config;
+use crate::executor::diagnostics::Diagnostic;
 use crate::executor::helpers::command::CommandBuilder;
 use crate::executor::helpers::env::is_codspeed_debug_enabled;
 use crate::executor::helpers::harvest_perf_maps_for_pids::harvest_perf_maps_for_pids;
@@ -45,6 +46,19 @@ pub mod unwind_data;
 const PERF_METADATA_CURRENT_VERSION: u64 = 1;
 const PERF_PIPEDATA_FILE_NAME: &str = "perf.pipedata";
 
+/// Reads an integer kernel parameter, e.g. `kernel.perf_event_paranoid`
+pub fn sysctl_read(name: &str) -> anyhow::Result<i64> {
+    let output = std::process::Command::new("sysctl").arg(name).output()?;
+    let output = String::from_utf8(output.stdout)?;
+
+    Ok(output
+        .split(" = ")
+        .last()
+        .context("Couldn't find the value in sysctl output")?
+        .trim()
+        .parse::<i64>()?)
+}
+
 pub struct PerfRunner {
     benchmark_data: OnceCell<BenchmarkData>,
 }
@@ -56,18 +70,6 @@ impl PerfRunner {
     ) -> anyhow::Result<()> {
         setup::install_perf(system_info, setup_cache_dir).await?;
 
-        let sysctl_read = |name: &str| -> anyhow::Result<i64> {
-            let output = std::process::Command::new("sysctl").arg(name).output()?;
-            let output = String::from_utf8(output.stdout)?;
-
-            Ok(output
-                .split(" = ")
-                .last()
-                .context("Couldn't find the value in sysctl output")?
-                .trim()
-                .parse::<i64>()?)
-        };
-
         // Allow access to kernel symbols
         if sysctl_read("kernel.kptr_restrict")? != 0 {
             run_with_sudo("sysctl", ["-w", "kernel.kptr_restrict=0"])?;
@@ -81,6 +83,56 @@ impl PerfRunner {
         Ok(())
     }
 
+    /// Checks that perf is installed, its capabilities and the kernel parameters it relies on
+    pub fn diagnose_environment() -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        match get_working_perf_executable() {
+            None => diagnostics.push(Diagnostic::warn(
+                "perf",
+                "not installed or not working, it will be installed on the next run",
+            )),
+            Some(perf_executable) => {
+                diagnostics.push(Diagnostic::pass(
+                    "perf",
+                    format!("found {}", perf_executable.to_string_lossy()),
+                ));
+                diagnostics.push(match get_compression_flags(&perf_executable) {
+                    Ok(Some(_)) => Diagnostic::pass("perf compression", "zstd is supported"),
+                    Ok(None) => Diagnostic::warn(
+                        "perf compression",
+                        "perf was built without zstd support, profiles will be larger",
+                    ),
+                    Err(e) => Diagnostic::fail("perf compression", e),
+                });
+                diagnostics.push(match get_event_flags(&perf_executable) {
+                    Ok(Some(_)) => Diagnostic::pass("perf events", "all events are available"),
+                    Ok(None) => Diagnostic::warn(
+                        "perf events",
+                        "some hardware events are missing, detailed event sampling is disabled",
+                    ),
+                    Err(e) => Diagnostic::fail("perf events", e),
+                });
+            }
+        }
+
+        for (name, expected) in [
+            ("kernel.kptr_restrict", 0),
+            ("kernel.perf_event_paranoid", -1),
+        ] {
+            diagnostics.push(match sysctl_read(name) {
+                Ok(value) if value == expected => Diagnostic::pass(name, value),
+                Ok(value) => Diagnostic::warn(
+                    name,
+                    format!("is {value}, it will be set to {expected} with sudo on the next run"),
+                ),
+                Err(e) => Diagnostic::fail(name, format!("failed to read the value: {e}")),
+            });
+        }
+
+        diagnostics
+    }
+
     pub fn new() -> Self {
         Self {
             benchmark_data: OnceCell::new(),
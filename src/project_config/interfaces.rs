@@ -1,5 +1,11 @@
+use crate::cli::UnwindingMode;
+use crate::prelude::*;
+use crate::runner_mode::RunnerMode;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
 
 /// Project-level configuration from codspeed.yaml file
 ///
@@ -8,10 +14,39 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize, Serialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct ProjectConfig {
+    /// Path of a config file to extend (relative to this file), its options are overridden by
+    /// the ones of this file and its benchmarks come before the ones of this file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// Glob patterns of config files whose benchmarks are added to the ones of this file
+    /// (relative to this file). Their working directories are relative to the file defining them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
     /// Default options to apply to all benchmark runs
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<ProjectOptions>,
     /// List of benchmark targets to execute
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub benchmarks: Option<Vec<Target>>,
+    /// Modes to run the targets in, unless they define their own. Targets are run once per mode,
+    /// with a separate upload for each of them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modes: Option<Vec<RunnerMode>>,
+    /// Named profiles overriding the options, selected with `--profile`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<BTreeMap<String, Profile>>,
+}
+
+/// A named set of options, applied on top of the root options when selected
+#[derive(Debug, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct Profile {
+    /// Options overriding the root options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<ProjectOptions>,
+    /// Only run the benchmarks whose name matches one of the patterns, in the format of `--bench`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub benchmarks: Option<Vec<String>>,
 }
 
 /// A benchmark target to execute
@@ -19,13 +54,153 @@ pub struct ProjectConfig {
 #[serde(rename_all = "kebab-case")]
 pub struct Target {
     /// Optional name for this target
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
-    /// Command to execute
-    pub exec: String,
+    /// Command to benchmark with the exec harness
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec: Option<String>,
+    /// Command running benchmarks that use a CodSpeed integration (e.g., "cargo codspeed run"),
+    /// run as is instead of being benchmarked with the exec harness
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run: Option<String>,
+    /// Environment variables to set when executing the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<BTreeMap<String, String>>,
+    /// Directory where the command will be executed (relative to config file)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+    /// Maximum duration of a single execution, after which it is killed (e.g., "30s", "5m")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    /// Parameters to expand the target over, each combination of values runs as a separate
+    /// benchmark with the `{{name}}` placeholders of `exec`, `env`, `stdin` and hooks substituted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matrix: Option<BTreeMap<String, Vec<MatrixValue>>>,
+    /// Modes to run this target in, overriding the root `modes` and the mode of the run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modes: Option<Vec<RunnerMode>>,
+    /// Shell command run once before the benchmark, excluded from the measurement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setup: Option<String>,
+    /// Shell command run once after the benchmark, even if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub teardown: Option<String>,
+    /// Shell command run before each round of executions, excluded from the measurement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_each: Option<String>,
+    /// Shell command run after each round of executions, excluded from the measurement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_each: Option<String>,
+    /// Exit code expected from every execution, instead of 0 (e.g., 1 for a linter finding issues)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expect_exit_code: Option<i32>,
+    /// Hex encoded SHA-256 checksum expected from the standard output of the first execution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expect_stdout_sha256: Option<String>,
+    /// Regex that the standard output of the first execution must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expect_stdout_regex: Option<String>,
+    /// File fed to the standard input of each execution (relative to config file)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdin: Option<String>,
+    /// Where the standard output of the command is written (defaults to inherit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<OutputTarget>,
+    /// Where the standard error of the command is written (defaults to inherit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<OutputTarget>,
     /// Target-specific options
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<TargetOptions>,
 }
 
+impl Target {
+    /// Command of the target, whether it is an `exec` or a `run` target
+    pub fn command(&self) -> &str {
+        self.exec
+            .as_deref()
+            .or(self.run.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Name identifying the target, the command is used when it has no explicit name
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(self.command())
+    }
+}
+
+/// Destination of an output stream of an `exec` target
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputTarget {
+    /// Write to the output of the run
+    Inherit,
+    /// Discard the output, keeping the throughput of the terminal or CI logs out of the measures
+    Null,
+    /// Write the output of the last execution to `outputs/<benchmark name>.<stream>` in the
+    /// profile folder
+    File,
+}
+
+/// Value of a matrix parameter
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum MatrixValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl Display for MatrixValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatrixValue::String(value) => write!(f, "{value}"),
+            MatrixValue::Integer(value) => write!(f, "{value}"),
+            MatrixValue::Float(value) => write!(f, "{value}"),
+            MatrixValue::Boolean(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Number of executions of the command in each round
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ItersPerRound {
+    Count(u64),
+    /// Chosen during warmup so that a round lasts at least 1ms
+    Auto(Auto),
+}
+
+/// The `auto` value of an option
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Auto {
+    Auto,
+}
+
+impl Display for ItersPerRound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItersPerRound::Count(count) => write!(f, "{count}"),
+            ItersPerRound::Auto(_) => write!(f, "auto"),
+        }
+    }
+}
+
+impl FromStr for ItersPerRound {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "auto" => Ok(ItersPerRound::Auto(Auto::Auto)),
+            count => Ok(ItersPerRound::Count(count.parse().with_context(|| {
+                format!("Invalid iters-per-round: '{count}'. Expected a positive integer or 'auto'")
+            })?)),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct TargetOptions {
@@ -34,28 +209,83 @@ pub struct TargetOptions {
 }
 
 /// Root-level options that apply to all benchmark runs unless overridden by CLI
-#[derive(Debug, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct ProjectOptions {
     /// Working directory where commands will be executed (relative to config file)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub working_directory: Option<String>,
+    /// Mode to run the benchmarks in, takes precedence over the mode of the shell session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<RunnerMode>,
+    /// Enable the linux perf profiler to collect granular performance data (defaults to true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_perf: Option<bool>,
+    /// Unwinding mode used with perf to collect the call stack
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub perf_unwinding_mode: Option<UnwindingMode>,
+    /// Allow runs without any benchmarks to succeed instead of failing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_empty: Option<bool>,
+    /// Instruments to enable with `codspeed run` (e.g., "mongodb")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruments: Option<Vec<String>>,
+    /// Name of the environment variable that contains the MongoDB URI to patch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mongo_uri_env_name: Option<String>,
+    /// Interleave the rounds of the `exec` targets instead of running each target to completion
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interleave: Option<bool>,
+    /// Shuffle the order of the interleaved rounds of the `exec` targets, implies `interleave`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shuffle: Option<bool>,
+    /// Seed of the shuffled order of the rounds, implies `shuffle` (defaults to random)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shuffle_seed: Option<u64>,
     /// Walltime execution configuration (flattened)
     #[serde(flatten)]
     pub walltime: Option<WalltimeOptions>,
 }
 
 /// Walltime execution options matching WalltimeExecutionArgs structure
-#[derive(Debug, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct WalltimeOptions {
     /// Duration of warmup phase (e.g., "1s", "500ms")
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub warmup_time: Option<String>,
     /// Maximum total execution time
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_time: Option<String>,
     /// Minimum total execution time
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub min_time: Option<String>,
     /// Maximum number of rounds
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_rounds: Option<u64>,
     /// Minimum number of rounds
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub min_rounds: Option<u64>,
+    /// Number of executions of the command in each round, or `auto` to choose it during warmup
+    /// so that a round lasts at least 1ms (defaults to 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iters_per_round: Option<ItersPerRound>,
+    /// Relative precision of the mean to reach before stopping the rounds (e.g., "1%"), still
+    /// capped by the maximum time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_precision: Option<String>,
+    /// CPUs the benchmarked processes are pinned to (e.g., "2", "0,2-3")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_affinity: Option<String>,
+    /// Nice value of the benchmarked processes, from -20 to 19, negative values being skipped
+    /// when not permitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nice: Option<i32>,
+    /// Real-time priority of the benchmarked processes, from 1 to 99, scheduled with the
+    /// SCHED_FIFO policy when permitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sched_fifo: Option<u32>,
+    /// Disable the address space layout randomization of the benchmarked processes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_aslr: Option<bool>,
 }
@@ -0,0 +1,281 @@
+use crate::cli::run::helpers::find_repository_root;
+use crate::prelude::*;
+use crate::run_environment::get_commit_hash_default_impl;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::cell::OnceCell;
+use std::path::Path;
+
+use super::{ProjectConfig, ProjectOptions, Target, WalltimeOptions};
+
+lazy_static! {
+    /// Variable reference, e.g. `${VAR}` or `${VAR:-default}`, escaped by doubling the `$`
+    static ref VARIABLE: Regex =
+        Regex::new(r"\$(\$?)\{([A-Za-z_][A-Za-z0-9_.]*)(?::-([^}]*))?\}").unwrap();
+}
+
+/// Replace the `${VAR}` and `${VAR:-default}` references of a value with the values returned
+/// by `lookup`, failing on undefined variables without default.
+/// Unbraced references such as `$VAR` are left to the shell.
+fn interpolate(value: &str, lookup: &impl Fn(&str) -> Result<Option<String>>) -> Result<String> {
+    let mut error = None;
+    let interpolated = VARIABLE.replace_all(value, |captures: &Captures| {
+        // `$${VAR}` is kept as a literal `${VAR}`
+        if !captures[1].is_empty() {
+            return captures[0][1..].to_string();
+        }
+
+        let name = &captures[2];
+        match lookup(name) {
+            Ok(Some(value)) => value,
+            Ok(None) => match captures.get(3) {
+                Some(default) => default.as_str().to_string(),
+                None => {
+                    error.get_or_insert_with(|| {
+                        anyhow!(
+                            "Undefined variable `{name}` in `{value}`, set it or give it a default with `${{{name}:-default}}`"
+                        )
+                    });
+                    String::new()
+                }
+            },
+            Err(e) => {
+                error.get_or_insert(e.context(format!("Failed to resolve `{name}` in `{value}`")));
+                String::new()
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(interpolated.into_owned()),
+    }
+}
+
+/// Interpolate a value in place with the variables of `lookup`
+fn interpolate_in_place(
+    lookup: &impl Fn(&str) -> Result<Option<String>>,
+) -> impl Fn(&mut String) -> Result<()> {
+    move |value| {
+        *value = interpolate(value, lookup)?;
+        Ok(())
+    }
+}
+
+/// Variables of a config file located in `config_dir`, the built-ins being resolved once per
+/// file
+struct Variables<'a> {
+    config_dir: &'a Path,
+    git_sha: OnceCell<String>,
+}
+
+impl<'a> Variables<'a> {
+    fn new(config_dir: &'a Path) -> Self {
+        Self {
+            config_dir,
+            git_sha: OnceCell::new(),
+        }
+    }
+
+    /// Look up a variable in the environment, or one of the built-ins relative to the config
+    /// directory:
+    /// - `git.sha`: the commit checked out in the repository
+    /// - `repo.root`: the root directory of the repository
+    fn lookup(&self, name: &str) -> Result<Option<String>> {
+        match name {
+            "git.sha" => {
+                if let Some(git_sha) = self.git_sha.get() {
+                    return Ok(Some(git_sha.clone()));
+                }
+                let repository_root = self.repository_root()?;
+                let git_sha = get_commit_hash_default_impl(&repository_root.to_string_lossy())?;
+                Ok(Some(self.git_sha.get_or_init(|| git_sha).clone()))
+            }
+            "repo.root" => Ok(Some(self.repository_root()?.to_string_lossy().into_owned())),
+            _ => Ok(std::env::var(name).ok()),
+        }
+    }
+
+    fn repository_root(&self) -> Result<std::path::PathBuf> {
+        find_repository_root(self.config_dir).with_context(|| {
+            format!(
+                "{} is not in a git repository",
+                self.config_dir.to_string_lossy()
+            )
+        })
+    }
+}
+
+impl ProjectConfig {
+    /// Interpolate the variables of the commands, working directories and option values of a
+    /// config file located in `config_dir`
+    pub(crate) fn interpolate_variables(&mut self, config_dir: &Path) -> Result<()> {
+        let variables = Variables::new(config_dir);
+        let lookup = |name: &str| variables.lookup(name);
+        let interpolate_value = interpolate_in_place(&lookup);
+
+        if let Some(options) = &mut self.options {
+            interpolate_options(options, &interpolate_value).context("Invalid root options")?;
+        }
+
+        for target in self.benchmarks.iter_mut().flatten() {
+            interpolate_target(target, &lookup)
+                .with_context(|| format!("Invalid target {}", target.display_name()))?;
+        }
+
+        for (name, profile) in self.profiles.iter_mut().flatten() {
+            if let Some(options) = &mut profile.options {
+                interpolate_options(options, &interpolate_value)
+                    .with_context(|| format!("Invalid options of profile {name}"))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Interpolate the fields of a target, looking up its `env` before `lookup`
+fn interpolate_target(
+    target: &mut Target,
+    lookup: &impl Fn(&str) -> Result<Option<String>>,
+) -> Result<()> {
+    target
+        .env
+        .iter_mut()
+        .flatten()
+        .try_for_each(|(_, value)| interpolate_in_place(lookup)(value))?;
+
+    let env = target.env.clone().unwrap_or_default();
+    let lookup = |name: &str| match env.get(name) {
+        Some(value) => Ok(Some(value.clone())),
+        None => lookup(name),
+    };
+    let interpolate = interpolate_in_place(&lookup);
+
+    target.exec.iter_mut().try_for_each(&interpolate)?;
+    target.run.iter_mut().try_for_each(&interpolate)?;
+    target
+        .working_directory
+        .iter_mut()
+        .try_for_each(&interpolate)?;
+    target.stdin.iter_mut().try_for_each(&interpolate)?;
+    if let Some(walltime) = target.options.as_mut().and_then(|o| o.walltime.as_mut()) {
+        interpolate_walltime(walltime, &interpolate)?;
+    }
+    Ok(())
+}
+
+fn interpolate_options(
+    options: &mut ProjectOptions,
+    interpolate: &impl Fn(&mut String) -> Result<()>,
+) -> Result<()> {
+    options
+        .working_directory
+        .iter_mut()
+        .try_for_each(interpolate)?;
+    options
+        .mongo_uri_env_name
+        .iter_mut()
+        .try_for_each(interpolate)?;
+    options
+        .instruments
+        .iter_mut()
+        .flatten()
+        .try_for_each(interpolate)?;
+    if let Some(walltime) = &mut options.walltime {
+        interpolate_walltime(walltime, interpolate)?;
+    }
+    Ok(())
+}
+
+fn interpolate_walltime(
+    walltime: &mut WalltimeOptions,
+    interpolate: &impl Fn(&mut String) -> Result<()>,
+) -> Result<()> {
+    walltime.warmup_time.iter_mut().try_for_each(interpolate)?;
+    walltime.max_time.iter_mut().try_for_each(interpolate)?;
+    walltime.min_time.iter_mut().try_for_each(interpolate)?;
+    walltime
+        .target_precision
+        .iter_mut()
+        .try_for_each(interpolate)?;
+    walltime.cpu_affinity.iter_mut().try_for_each(interpolate)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Result<Option<String>> {
+        Ok(match name {
+            "BUILD_DIR" => Some("target/release".to_string()),
+            "git.sha" => Some("abc123".to_string()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(
+            interpolate("${BUILD_DIR}/bench --sha ${git.sha}", &lookup).unwrap(),
+            "target/release/bench --sha abc123"
+        );
+        assert_eq!(
+            interpolate("${THREADS:-4} ${BUILD_DIR:-build}", &lookup).unwrap(),
+            "4 target/release"
+        );
+        assert_eq!(interpolate("${EMPTY:-}", &lookup).unwrap(), "");
+
+        // Shell variables and escaped references are left untouched
+        assert_eq!(
+            interpolate("echo $HOME $${BUILD_DIR}", &lookup).unwrap(),
+            "echo $HOME ${BUILD_DIR}"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_undefined_variable() {
+        let error = interpolate("./bench ${MISSING}", &lookup).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Undefined variable `MISSING` in `./bench ${MISSING}`, set it or give it a default with `${MISSING:-default}`"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_variables() {
+        let mut config: ProjectConfig = serde_yaml::from_str(
+            r#"
+options:
+  max-time: ${CODSPEED_TEST_MAX_TIME:-5s}
+benchmarks:
+  - name: parse
+    exec: ${CODSPEED_TEST_BUILD_DIR:-target}/parse
+    working-directory: ${CODSPEED_TEST_BUILD_DIR:-target}
+  - exec: ${CODSPEED_TEST_BUILD_DIR}/io --threads ${CODSPEED_TEST_THREADS}
+    env:
+      CODSPEED_TEST_BUILD_DIR: ${CODSPEED_TEST_OUT_DIR:-out}
+      CODSPEED_TEST_THREADS: "4"
+  - exec: ./bench ${CODSPEED_TEST_UNDEFINED}
+"#,
+        )
+        .unwrap();
+
+        let error = config.interpolate_variables(Path::new(".")).unwrap_err();
+        assert!(
+            format!("{error:#}").starts_with("Invalid target ./bench ${CODSPEED_TEST_UNDEFINED}"),
+            "{error:#}"
+        );
+
+        config.benchmarks.as_mut().unwrap().pop();
+        config.interpolate_variables(Path::new(".")).unwrap();
+        let walltime = config.options.unwrap().walltime.unwrap();
+        assert_eq!(walltime.max_time.as_deref(), Some("5s"));
+        let targets = config.benchmarks.unwrap();
+        assert_eq!(targets[0].exec.as_deref(), Some("target/parse"));
+        assert_eq!(targets[0].working_directory.as_deref(), Some("target"));
+        // The environment of a target is looked up first
+        assert_eq!(targets[1].exec.as_deref(), Some("out/io --threads 4"));
+    }
+}
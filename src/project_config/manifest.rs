@@ -0,0 +1,120 @@
+use crate::prelude::*;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+/// Manifest file names that can embed the config, looked up after the codspeed.yaml files
+pub(crate) const MANIFEST_FILENAMES: &[&str] = &["pyproject.toml", "Cargo.toml"];
+
+/// Table of a manifest file holding the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestTable {
+    /// `[tool.codspeed]` of a pyproject.toml
+    PyProjectTool,
+    /// `[package.metadata.codspeed]` of a Cargo.toml
+    CargoPackage,
+    /// `[workspace.metadata.codspeed]` of a Cargo.toml
+    CargoWorkspace,
+}
+
+impl ManifestTable {
+    /// Tables that can hold the config in the manifest at `path`, in priority order
+    fn candidates(path: &Path) -> &'static [ManifestTable] {
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some("pyproject.toml") => &[Self::PyProjectTool],
+            Some("Cargo.toml") => &[Self::CargoPackage, Self::CargoWorkspace],
+            _ => &[],
+        }
+    }
+
+    fn keys(&self) -> &'static [&'static str] {
+        match self {
+            Self::PyProjectTool => &["tool", "codspeed"],
+            Self::CargoPackage => &["package", "metadata", "codspeed"],
+            Self::CargoWorkspace => &["workspace", "metadata", "codspeed"],
+        }
+    }
+}
+
+impl Display for ManifestTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}]", self.keys().join("."))
+    }
+}
+
+/// Whether the config at `path` is embedded in a manifest rather than a codspeed.yaml file
+pub fn is_manifest(path: &Path) -> bool {
+    !ManifestTable::candidates(path).is_empty()
+}
+
+/// Read the config table embedded in the manifest at `path`
+///
+/// Returns `Ok(None)` if the manifest does not define any of the tables holding the config.
+pub fn read_manifest_table(path: &Path) -> Result<Option<(ManifestTable, toml::Value)>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read manifest at {}", path.display()))?;
+    let manifest: toml::Table = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse manifest at {}", path.display()))?;
+
+    for table in ManifestTable::candidates(path) {
+        let (first, rest) = table.keys().split_first().expect("keys are not empty");
+        let mut value = manifest.get(*first);
+        for key in rest {
+            value = value.and_then(|value| value.get(*key));
+        }
+        if let Some(value) = value {
+            return Ok(Some((*table, value.clone())));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Describe the source of the config at `path`, e.g. `[tool.codspeed] in /repo/pyproject.toml`
+///
+/// Manifests whose table can't be read are described by their path only.
+pub fn describe_source(path: &Path) -> String {
+    if is_manifest(path) {
+        if let Ok(Some((table, _))) = read_manifest_table(path) {
+            return format!("{table} in {}", path.display());
+        }
+    }
+    path.display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_manifest_table() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let pyproject = temp_dir.path().join("pyproject.toml");
+        fs::write(
+            &pyproject,
+            "[project]\nname = \"app\"\n\n[tool.codspeed.options]\nmax-time = \"5s\"\n",
+        )
+        .unwrap();
+        let (table, value) = read_manifest_table(&pyproject).unwrap().unwrap();
+        assert_eq!(table, ManifestTable::PyProjectTool);
+        assert_eq!(value["options"]["max-time"].as_str(), Some("5s"));
+
+        let cargo = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &cargo,
+            "[workspace]\nmembers = [\"app\"]\n\n[workspace.metadata.codspeed]\nmodes = [\"walltime\"]\n",
+        )
+        .unwrap();
+        let (table, _) = read_manifest_table(&cargo).unwrap().unwrap();
+        assert_eq!(table, ManifestTable::CargoWorkspace);
+        assert_eq!(
+            describe_source(&cargo),
+            format!("[workspace.metadata.codspeed] in {}", cargo.display())
+        );
+
+        fs::write(&cargo, "[package]\nname = \"app\"\n").unwrap();
+        assert!(read_manifest_table(&cargo).unwrap().is_none());
+    }
+}
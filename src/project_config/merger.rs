@@ -1,11 +1,13 @@
 use crate::cli::ExecAndRunSharedArgs;
-use exec_harness::walltime::WalltimeExecutionArgs;
+use exec_harness::walltime::{ProcessSettingsArgs, SchedulingArgs, WalltimeExecutionArgs};
 
 use super::{ProjectOptions, WalltimeOptions};
 
 /// Handles merging of CLI arguments with project configuration
 ///
-/// Implements the precedence rule: CLI > config > None
+/// Implements the precedence rule: CLI > config > None.
+/// Values set through environment variables are parsed by clap as CLI arguments, so they also
+/// take precedence over the config.
 pub struct ConfigMerger;
 
 impl ConfigMerger {
@@ -32,6 +34,34 @@ impl ConfigMerger {
             ),
             max_rounds: cli.max_rounds.or(config_opts.and_then(|c| c.max_rounds)),
             min_rounds: cli.min_rounds.or(config_opts.and_then(|c| c.min_rounds)),
+            iters_per_round: cli.iters_per_round.clone().or(config_opts
+                .and_then(|c| c.iters_per_round)
+                .map(|iters_per_round| iters_per_round.to_string())),
+            spawn_overhead: cli.spawn_overhead,
+            target_precision: Self::merge_option(
+                &cli.target_precision,
+                config_opts.and_then(|c| c.target_precision.as_ref()),
+            ),
+            process_settings: Self::merge_process_settings(&cli.process_settings, config_opts),
+        }
+    }
+
+    /// Merge the process settings of the benchmarks with project config walltime options
+    ///
+    /// CLI arguments take precedence over config values, the CLI flag can only disable ASLR.
+    pub fn merge_process_settings(
+        cli: &ProcessSettingsArgs,
+        config_opts: Option<&WalltimeOptions>,
+    ) -> ProcessSettingsArgs {
+        ProcessSettingsArgs {
+            cpu_affinity: Self::merge_option(
+                &cli.cpu_affinity,
+                config_opts.and_then(|c| c.cpu_affinity.as_ref()),
+            ),
+            nice: cli.nice.or(config_opts.and_then(|c| c.nice)),
+            sched_fifo: cli.sched_fifo.or(config_opts.and_then(|c| c.sched_fifo)),
+            disable_aslr: cli.disable_aslr
+                || config_opts.and_then(|c| c.disable_aslr).unwrap_or(false),
         }
     }
 
@@ -44,17 +74,133 @@ impl ConfigMerger {
         config_opts: Option<&ProjectOptions>,
     ) -> ExecAndRunSharedArgs {
         let mut merged = cli.clone();
+        let Some(opts) = config_opts else {
+            return merged;
+        };
 
-        // Merge working_directory if not set via CLI
-        if merged.working_directory.is_none() {
-            if let Some(opts) = config_opts {
-                merged.working_directory = opts.working_directory.clone();
-            }
-        }
+        merged.working_directory =
+            Self::merge_option(&cli.working_directory, opts.working_directory.as_ref());
+        merged.mode = Self::merge_option(&cli.mode, opts.mode.as_ref());
+        merged.perf_run_args.enable_perf =
+            Self::merge_option(&cli.perf_run_args.enable_perf, opts.enable_perf.as_ref());
+        merged.perf_run_args.perf_unwinding_mode = Self::merge_option(
+            &cli.perf_run_args.perf_unwinding_mode,
+            opts.perf_unwinding_mode.as_ref(),
+        );
+        // The CLI flag can only enable it
+        merged.allow_empty = cli.allow_empty || opts.allow_empty.unwrap_or(false);
 
         merged
     }
 
+    /// Merge the instruments of the run command with project config options
+    ///
+    /// Instruments given on the CLI replace the ones of the config instead of being added to them.
+    pub fn merge_instruments(
+        cli_instruments: &[String],
+        cli_mongo_uri_env_name: &Option<String>,
+        config_opts: Option<&ProjectOptions>,
+    ) -> (Vec<String>, Option<String>) {
+        let instruments = match config_opts.and_then(|c| c.instruments.as_ref()) {
+            Some(config_instruments) if cli_instruments.is_empty() => config_instruments.clone(),
+            _ => cli_instruments.to_vec(),
+        };
+        let mongo_uri_env_name = Self::merge_option(
+            cli_mongo_uri_env_name,
+            config_opts.and_then(|c| c.mongo_uri_env_name.as_ref()),
+        );
+
+        (instruments, mongo_uri_env_name)
+    }
+
+    /// Merge the scheduling args of the exec-harness benchmarks with project config options
+    ///
+    /// The CLI flags can only enable interleaving and shuffling, a CLI seed takes precedence.
+    pub fn merge_scheduling_args(
+        cli: &SchedulingArgs,
+        config_opts: Option<&ProjectOptions>,
+    ) -> SchedulingArgs {
+        let Some(opts) = config_opts else {
+            return cli.clone();
+        };
+
+        SchedulingArgs {
+            interleave: cli.interleave || opts.interleave.unwrap_or(false),
+            shuffle: cli.shuffle || opts.shuffle.unwrap_or(false),
+            shuffle_seed: cli.shuffle_seed.or(opts.shuffle_seed),
+        }
+    }
+
+    /// Merge options overriding the root options of the config, from the selected profile or from
+    /// a config file extending another one
+    ///
+    /// Overriding values take precedence over root values, CLI arguments are merged afterwards and
+    /// take precedence over both.
+    pub fn merge_project_options(
+        base: Option<&ProjectOptions>,
+        overrides: Option<&ProjectOptions>,
+    ) -> Option<ProjectOptions> {
+        let Some(overrides) = overrides else {
+            return base.cloned();
+        };
+        let Some(base) = base else {
+            return Some(overrides.clone());
+        };
+
+        Some(ProjectOptions {
+            working_directory: Self::merge_option(
+                &overrides.working_directory,
+                base.working_directory.as_ref(),
+            ),
+            mode: Self::merge_option(&overrides.mode, base.mode.as_ref()),
+            enable_perf: overrides.enable_perf.or(base.enable_perf),
+            perf_unwinding_mode: overrides.perf_unwinding_mode.or(base.perf_unwinding_mode),
+            allow_empty: overrides.allow_empty.or(base.allow_empty),
+            instruments: Self::merge_option(&overrides.instruments, base.instruments.as_ref()),
+            mongo_uri_env_name: Self::merge_option(
+                &overrides.mongo_uri_env_name,
+                base.mongo_uri_env_name.as_ref(),
+            ),
+            interleave: overrides.interleave.or(base.interleave),
+            shuffle: overrides.shuffle.or(base.shuffle),
+            shuffle_seed: overrides.shuffle_seed.or(base.shuffle_seed),
+            walltime: Self::merge_walltime_overrides(
+                base.walltime.as_ref(),
+                overrides.walltime.as_ref(),
+            ),
+        })
+    }
+
+    /// Merge walltime options overriding other walltime options of the config
+    pub fn merge_walltime_overrides(
+        base: Option<&WalltimeOptions>,
+        overrides: Option<&WalltimeOptions>,
+    ) -> Option<WalltimeOptions> {
+        let Some(overrides) = overrides else {
+            return base.cloned();
+        };
+        let Some(base) = base else {
+            return Some(overrides.clone());
+        };
+
+        Some(WalltimeOptions {
+            warmup_time: Self::merge_option(&overrides.warmup_time, base.warmup_time.as_ref()),
+            max_time: Self::merge_option(&overrides.max_time, base.max_time.as_ref()),
+            min_time: Self::merge_option(&overrides.min_time, base.min_time.as_ref()),
+            max_rounds: overrides.max_rounds.or(base.max_rounds),
+            min_rounds: overrides.min_rounds.or(base.min_rounds),
+            iters_per_round: overrides.iters_per_round.or(base.iters_per_round),
+            target_precision: Self::merge_option(
+                &overrides.target_precision,
+                base.target_precision.as_ref(),
+            ),
+            cpu_affinity: Self::merge_option(&overrides.cpu_affinity, base.cpu_affinity.as_ref()),
+            nice: overrides.nice.or(base.nice),
+            sched_fifo: overrides.sched_fifo.or(base.sched_fifo),
+            disable_aslr: overrides.disable_aslr.or(base.disable_aslr),
+        })
+    }
+
     /// Helper to merge Option values with precedence: CLI > config > None
     fn merge_option<T: Clone>(cli_value: &Option<T>, config_value: Option<&T>) -> Option<T> {
         cli_value.clone().or_else(|| config_value.cloned())
@@ -64,7 +210,7 @@ impl ConfigMerger {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli::PerfRunArgs;
+    use crate::cli::{PerfRunArgs, UnwindingMode};
     use crate::runner_mode::RunnerMode;
 
     #[test]
@@ -75,6 +221,10 @@ mod tests {
             min_time: None,
             max_rounds: Some(50),
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         };
 
         let config = WalltimeOptions {
@@ -83,6 +233,12 @@ mod tests {
             min_time: Some("2s".to_string()),
             max_rounds: Some(100),
             min_rounds: Some(10),
+            iters_per_round: None,
+            target_precision: None,
+            cpu_affinity: None,
+            nice: None,
+            sched_fifo: None,
+            disable_aslr: None,
         };
 
         let merged = ConfigMerger::merge_walltime_options(&cli, Some(&config));
@@ -104,6 +260,10 @@ mod tests {
             min_time: None,
             max_rounds: None,
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         };
 
         let config = WalltimeOptions {
@@ -112,6 +272,12 @@ mod tests {
             min_time: None,
             max_rounds: Some(200),
             min_rounds: None,
+            iters_per_round: None,
+            target_precision: None,
+            cpu_affinity: None,
+            nice: None,
+            sched_fifo: None,
+            disable_aslr: None,
         };
 
         let merged = ConfigMerger::merge_walltime_options(&cli, Some(&config));
@@ -124,6 +290,35 @@ mod tests {
         assert_eq!(merged.min_rounds, None);
     }
 
+    #[test]
+    fn test_merge_process_settings() {
+        let cli = ProcessSettingsArgs {
+            cpu_affinity: Some("2".to_string()),
+            ..Default::default()
+        };
+        let config = WalltimeOptions {
+            warmup_time: None,
+            max_time: None,
+            min_time: None,
+            max_rounds: None,
+            min_rounds: None,
+            iters_per_round: None,
+            target_precision: None,
+            cpu_affinity: Some("0-3".to_string()),
+            nice: Some(-5),
+            sched_fifo: None,
+            disable_aslr: Some(true),
+        };
+
+        let merged = ConfigMerger::merge_process_settings(&cli, Some(&config));
+        assert_eq!(merged.cpu_affinity, Some("2".to_string()));
+        assert_eq!(merged.nice, Some(-5));
+        assert_eq!(merged.sched_fifo, None);
+        assert!(merged.disable_aslr);
+
+        assert_eq!(ConfigMerger::merge_process_settings(&cli, None), cli);
+    }
+
     #[test]
     fn test_merge_walltime_no_config() {
         let cli = WalltimeExecutionArgs {
@@ -132,6 +327,10 @@ mod tests {
             min_time: None,
             max_rounds: Some(30),
             min_rounds: None,
+            iters_per_round: None,
+            spawn_overhead: None,
+            target_precision: None,
+            process_settings: Default::default(),
         };
 
         let merged = ConfigMerger::merge_walltime_options(&cli, None);
@@ -160,7 +359,7 @@ mod tests {
             allow_empty: false,
             go_runner_version: None,
             perf_run_args: PerfRunArgs {
-                enable_perf: true,
+                enable_perf: Some(true),
                 perf_unwinding_mode: None,
             },
         };
@@ -168,6 +367,7 @@ mod tests {
         let config = ProjectOptions {
             walltime: None,
             working_directory: Some("./config-dir".to_string()),
+            ..Default::default()
         };
 
         let merged = ConfigMerger::merge_shared_args(&cli, Some(&config));
@@ -192,7 +392,7 @@ mod tests {
             allow_empty: false,
             go_runner_version: None,
             perf_run_args: PerfRunArgs {
-                enable_perf: true,
+                enable_perf: Some(true),
                 perf_unwinding_mode: None,
             },
         };
@@ -200,6 +400,7 @@ mod tests {
         let config = ProjectOptions {
             walltime: None,
             working_directory: Some("./config-dir".to_string()),
+            ..Default::default()
         };
 
         let merged = ConfigMerger::merge_shared_args(&cli, Some(&config));
@@ -226,7 +427,7 @@ mod tests {
             allow_empty: false,
             go_runner_version: None,
             perf_run_args: PerfRunArgs {
-                enable_perf: false,
+                enable_perf: Some(false),
                 perf_unwinding_mode: None,
             },
         };
@@ -238,6 +439,123 @@ mod tests {
         assert_eq!(merged.mode, Some(RunnerMode::Simulation));
     }
 
+    fn unset_shared_args() -> ExecAndRunSharedArgs {
+        ExecAndRunSharedArgs {
+            upload_url: None,
+            token: None,
+            repository: None,
+            provider: None,
+            working_directory: None,
+            mode: None,
+            profile_folder: None,
+            skip_upload: false,
+            skip_run: false,
+            skip_setup: false,
+            allow_empty: false,
+            go_runner_version: None,
+            perf_run_args: PerfRunArgs {
+                enable_perf: None,
+                perf_unwinding_mode: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_merge_shared_args_run_options_from_config() {
+        let cli = unset_shared_args();
+
+        let config = ProjectOptions {
+            mode: Some(RunnerMode::Walltime),
+            enable_perf: Some(false),
+            perf_unwinding_mode: Some(UnwindingMode::FramePointer),
+            allow_empty: Some(true),
+            ..Default::default()
+        };
+
+        let merged = ConfigMerger::merge_shared_args(&cli, Some(&config));
+
+        assert_eq!(merged.mode, Some(RunnerMode::Walltime));
+        assert_eq!(merged.perf_run_args.enable_perf, Some(false));
+        assert!(!merged.perf_run_args.is_perf_enabled());
+        assert_eq!(
+            merged.perf_run_args.perf_unwinding_mode,
+            Some(UnwindingMode::FramePointer)
+        );
+        assert!(merged.allow_empty);
+    }
+
+    #[test]
+    fn test_merge_shared_args_run_options_from_cli() {
+        let cli = ExecAndRunSharedArgs {
+            mode: Some(RunnerMode::Simulation),
+            allow_empty: true,
+            perf_run_args: PerfRunArgs {
+                enable_perf: Some(true),
+                perf_unwinding_mode: Some(UnwindingMode::Dwarf),
+            },
+            ..unset_shared_args()
+        };
+
+        let config = ProjectOptions {
+            mode: Some(RunnerMode::Walltime),
+            enable_perf: Some(false),
+            perf_unwinding_mode: Some(UnwindingMode::FramePointer),
+            allow_empty: Some(false),
+            ..Default::default()
+        };
+
+        let merged = ConfigMerger::merge_shared_args(&cli, Some(&config));
+
+        // CLI values, which include the ones set from the environment, should win
+        assert_eq!(merged.mode, Some(RunnerMode::Simulation));
+        assert_eq!(merged.perf_run_args.enable_perf, Some(true));
+        assert_eq!(
+            merged.perf_run_args.perf_unwinding_mode,
+            Some(UnwindingMode::Dwarf)
+        );
+        assert!(merged.allow_empty);
+    }
+
+    #[test]
+    fn test_merge_shared_args_perf_enabled_by_default() {
+        let merged =
+            ConfigMerger::merge_shared_args(&unset_shared_args(), Some(&ProjectOptions::default()));
+
+        assert_eq!(merged.mode, None);
+        assert_eq!(merged.perf_run_args.enable_perf, None);
+        assert!(merged.perf_run_args.is_perf_enabled());
+        assert!(!merged.allow_empty);
+    }
+
+    #[test]
+    fn test_merge_instruments() {
+        let config = ProjectOptions {
+            instruments: Some(vec!["mongodb".to_string()]),
+            mongo_uri_env_name: Some("MONGODB_URI".to_string()),
+            ..Default::default()
+        };
+
+        // Config values are used when not set via CLI
+        let (instruments, mongo_uri_env_name) =
+            ConfigMerger::merge_instruments(&[], &None, Some(&config));
+        assert_eq!(instruments, vec!["mongodb".to_string()]);
+        assert_eq!(mongo_uri_env_name, Some("MONGODB_URI".to_string()));
+
+        // CLI values win
+        let (instruments, mongo_uri_env_name) = ConfigMerger::merge_instruments(
+            &["other".to_string()],
+            &Some("DATABASE_URL".to_string()),
+            Some(&config),
+        );
+        assert_eq!(instruments, vec!["other".to_string()]);
+        assert_eq!(mongo_uri_env_name, Some("DATABASE_URL".to_string()));
+
+        // No config
+        let (instruments, mongo_uri_env_name) = ConfigMerger::merge_instruments(&[], &None, None);
+        assert!(instruments.is_empty());
+        assert_eq!(mongo_uri_env_name, None);
+    }
+
     #[test]
     fn test_merge_option_helper() {
         // CLI value wins
@@ -258,4 +576,84 @@ mod tests {
         let result = ConfigMerger::merge_option(&cli_val, config_val.as_ref());
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_merge_scheduling_args() {
+        let config = ProjectOptions {
+            shuffle: Some(true),
+            shuffle_seed: Some(42),
+            ..Default::default()
+        };
+
+        let merged = ConfigMerger::merge_scheduling_args(
+            &SchedulingArgs {
+                interleave: true,
+                shuffle: false,
+                shuffle_seed: Some(7),
+            },
+            Some(&config),
+        );
+        assert!(merged.interleave);
+        assert!(merged.shuffle);
+        // CLI seed should win
+        assert_eq!(merged.shuffle_seed, Some(7));
+
+        let merged = ConfigMerger::merge_scheduling_args(&SchedulingArgs::default(), None);
+        assert!(!merged.is_interleaved());
+    }
+
+    #[test]
+    fn test_merge_project_options() {
+        let base = ProjectOptions {
+            mode: Some(RunnerMode::Simulation),
+            enable_perf: Some(false),
+            walltime: Some(WalltimeOptions {
+                warmup_time: Some("1s".to_string()),
+                max_time: Some("3s".to_string()),
+                min_time: None,
+                max_rounds: None,
+                min_rounds: None,
+                iters_per_round: None,
+                target_precision: None,
+                cpu_affinity: None,
+                nice: None,
+                sched_fifo: None,
+                disable_aslr: None,
+            }),
+            ..Default::default()
+        };
+        let profile = ProjectOptions {
+            mode: Some(RunnerMode::Walltime),
+            walltime: Some(WalltimeOptions {
+                warmup_time: None,
+                max_time: Some("30s".to_string()),
+                min_time: None,
+                max_rounds: None,
+                min_rounds: None,
+                iters_per_round: None,
+                target_precision: None,
+                cpu_affinity: None,
+                nice: None,
+                sched_fifo: None,
+                disable_aslr: None,
+            }),
+            ..Default::default()
+        };
+
+        let merged = ConfigMerger::merge_project_options(Some(&base), Some(&profile)).unwrap();
+        assert_eq!(merged.mode, Some(RunnerMode::Walltime));
+        assert_eq!(merged.enable_perf, Some(false));
+        let walltime = merged.walltime.unwrap();
+        assert_eq!(walltime.warmup_time, Some("1s".to_string()));
+        assert_eq!(walltime.max_time, Some("30s".to_string()));
+
+        assert_eq!(
+            ConfigMerger::merge_project_options(Some(&base), None),
+            Some(base.clone())
+        );
+        assert_eq!(
+            ConfigMerger::merge_project_options(None, Some(&profile)),
+            Some(profile)
+        );
+    }
 }
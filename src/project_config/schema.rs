@@ -0,0 +1,450 @@
+//! Validation of a configuration file against the JSON schema generated from [`ProjectConfig`]
+//!
+//! Only the subset of JSON schema emitted by `schemars` for the config structures is supported.
+//!
+//! [`ProjectConfig`]: super::ProjectConfig
+
+use crate::prelude::*;
+use serde_json::{Map, Value};
+use std::fmt::Display;
+
+/// Schema generated by `cargo run --bin generate-config-schema`
+const SCHEMA: &str = include_str!("../../schemas/codspeed.schema.json");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// A value of the configuration file not matching the schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaIssue {
+    pub severity: IssueSeverity,
+    pub path: Vec<PathSegment>,
+    /// 1-based line of the offending value, when it could be located in the file
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl SchemaIssue {
+    /// Dotted path of the offending value, e.g. `benchmarks[0].options.max-time`
+    pub fn path_display(&self) -> String {
+        if self.path.is_empty() {
+            return "(root)".into();
+        }
+
+        let mut path = String::new();
+        for segment in &self.path {
+            match segment {
+                PathSegment::Key(key) if path.is_empty() => path.push_str(key),
+                PathSegment::Key(key) => path.push_str(&format!(".{key}")),
+                PathSegment::Index(index) => path.push_str(&format!("[{index}]")),
+            }
+        }
+        path
+    }
+}
+
+/// Check the content of a configuration file against the generated JSON schema
+///
+/// YAML syntax errors are returned as errors, their message contains the line and column.
+pub fn check_schema(content: &str) -> Result<Vec<SchemaIssue>> {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(content)?;
+    let value = serde_json::to_value(yaml).context("Configuration keys must be strings")?;
+
+    Ok(check_schema_value(&value)?
+        .into_iter()
+        .map(|mut issue| {
+            issue.line = locate_line(content, &issue.path);
+            issue
+        })
+        .collect())
+}
+
+/// Check an already parsed configuration against the generated JSON schema, e.g. the table
+/// embedded in a manifest, the issues have no line
+pub fn check_schema_value(value: &Value) -> Result<Vec<SchemaIssue>> {
+    let schema: Value = serde_json::from_str(SCHEMA).context("Failed to parse the JSON schema")?;
+
+    let empty_definitions = Map::new();
+    let mut checker = SchemaChecker {
+        definitions: schema
+            .get("definitions")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty_definitions),
+        issues: vec![],
+    };
+    checker.check(&schema, value, &mut vec![]);
+
+    Ok(checker.issues)
+}
+
+struct SchemaChecker<'a> {
+    definitions: &'a Map<String, Value>,
+    issues: Vec<SchemaIssue>,
+}
+
+impl<'a> SchemaChecker<'a> {
+    fn report(&mut self, severity: IssueSeverity, path: &[PathSegment], message: impl Display) {
+        self.issues.push(SchemaIssue {
+            severity,
+            path: path.to_vec(),
+            line: None,
+            message: message.to_string(),
+        });
+    }
+
+    fn error_count(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == IssueSeverity::Error)
+            .count()
+    }
+
+    /// Follow `$ref` pointers to the schema definitions
+    fn resolve(&self, mut schema: &'a Value) -> &'a Value {
+        while let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match reference
+                .strip_prefix("#/definitions/")
+                .and_then(|name| self.definitions.get(name))
+            {
+                Some(definition) => schema = definition,
+                None => break,
+            }
+        }
+        schema
+    }
+
+    fn check(&mut self, schema: &'a Value, value: &Value, path: &mut Vec<PathSegment>) {
+        let schema = self.resolve(schema);
+
+        if let Some(branches) = schema.get("anyOf").and_then(Value::as_array) {
+            // Keep the issues of the branch matching best
+            let best = branches
+                .iter()
+                .map(|branch| {
+                    let mut checker = SchemaChecker {
+                        definitions: self.definitions,
+                        issues: vec![],
+                    };
+                    checker.check(branch, value, path);
+                    checker
+                })
+                .min_by_key(|checker| checker.error_count());
+            if let Some(best) = best {
+                self.issues.extend(best.issues);
+            }
+            return;
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let expected = match expected {
+                Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                Value::String(ty) => vec![ty.as_str()],
+                _ => vec![],
+            };
+            if !expected.iter().any(|ty| matches_type(ty, value)) {
+                self.report(
+                    IssueSeverity::Error,
+                    path,
+                    format!(
+                        "expected {}, found {}",
+                        expected.join(" or "),
+                        type_name(value)
+                    ),
+                );
+                return;
+            }
+        }
+
+        if let (Some(minimum), Some(number)) = (
+            schema.get("minimum").and_then(Value::as_f64),
+            value.as_f64(),
+        ) {
+            if number < minimum {
+                self.report(
+                    IssueSeverity::Error,
+                    path,
+                    format!("must be greater than or equal to {minimum}"),
+                );
+            }
+        }
+
+        if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+            if !value.is_null() && !variants.contains(value) {
+                self.report(
+                    IssueSeverity::Error,
+                    path,
+                    format!(
+                        "expected one of {}, found {value}",
+                        variants.iter().map(|v| v.to_string()).join(", ")
+                    ),
+                );
+            }
+        }
+
+        match value {
+            Value::Object(object) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                for required in schema
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                {
+                    if !object.contains_key(required) {
+                        self.report(
+                            IssueSeverity::Error,
+                            path,
+                            format!("missing required key `{required}`"),
+                        );
+                    }
+                }
+
+                for (key, value) in object {
+                    path.push(PathSegment::Key(key.clone()));
+                    let property_schema = properties
+                        .and_then(|properties| properties.get(key))
+                        .or_else(|| schema.get("additionalProperties"));
+                    match property_schema {
+                        Some(Value::Bool(false)) => {
+                            self.report(IssueSeverity::Error, path, format!("unknown key `{key}`"))
+                        }
+                        Some(property_schema) if property_schema.is_object() => {
+                            self.check(property_schema, value, path)
+                        }
+                        _ => self.report(
+                            IssueSeverity::Warning,
+                            path,
+                            format!("unknown key `{key}`, it will be ignored"),
+                        ),
+                    }
+                    path.pop();
+                }
+            }
+            Value::Array(items) => {
+                if let Some(items_schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        path.push(PathSegment::Index(index));
+                        self.check(items_schema, item, path);
+                        path.pop();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn matches_type(ty: &str, value: &Value) -> bool {
+    match ty {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Best effort lookup of the line holding the value at `path` in a block style YAML document
+///
+/// YAML values do not keep their position once parsed, so the keys and sequence items of the
+/// path are searched for line by line, using the indentation to stay within the parent value.
+fn locate_line(content: &str, path: &[PathSegment]) -> Option<usize> {
+    let lines = content.lines().collect::<Vec<_>>();
+    // Line of the value found so far, and indentation of its key or sequence item
+    let mut current: Option<usize> = None;
+    let mut parent_indent: isize = -1;
+    // The keys of a sequence item may start on the line of its `-`
+    let mut is_sequence_item = false;
+
+    for segment in path {
+        let start = match current {
+            None => 0,
+            Some(line) if is_sequence_item => line,
+            Some(line) => line + 1,
+        };
+        let mut found = None;
+        let mut item_indent = None;
+        let mut item_count = 0;
+
+        for (index, line) in lines.iter().enumerate().skip(start) {
+            let text = line.trim_start();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let indent = (line.len() - text.len()) as isize;
+
+            match segment {
+                PathSegment::Key(key) => {
+                    if indent <= parent_indent && current != Some(index) {
+                        break;
+                    }
+                    let mut key_indent = indent;
+                    let mut text = text;
+                    while let Some(rest) = text.strip_prefix("- ") {
+                        key_indent += 2 + (rest.len() - rest.trim_start().len()) as isize;
+                        text = rest.trim_start();
+                    }
+                    if key_indent > parent_indent && is_key_line(text, key) {
+                        found = Some((index, key_indent));
+                        break;
+                    }
+                }
+                PathSegment::Index(target) => {
+                    let is_item = text.starts_with('-');
+                    if indent < parent_indent || (indent == parent_indent && !is_item) {
+                        break;
+                    }
+                    if !is_item || *item_indent.get_or_insert(indent) != indent {
+                        continue;
+                    }
+                    if item_count == *target {
+                        found = Some((index, indent));
+                        break;
+                    }
+                    item_count += 1;
+                }
+            }
+        }
+
+        let (line, indent) = found?;
+        current = Some(line);
+        parent_indent = indent;
+        is_sequence_item = matches!(segment, PathSegment::Index(_));
+    }
+
+    current.map(|line| line + 1)
+}
+
+fn is_key_line(content: &str, key: &str) -> bool {
+    [key.to_string(), format!("\"{key}\""), format!("'{key}'")]
+        .iter()
+        .any(|candidate| {
+            content
+                .strip_prefix(candidate.as_str())
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(content: &str) -> Vec<(IssueSeverity, String, Option<usize>, String)> {
+        check_schema(content)
+            .unwrap()
+            .into_iter()
+            .map(|issue| {
+                (
+                    issue.severity,
+                    issue.path_display(),
+                    issue.line,
+                    issue.message.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_config() {
+        let content = r#"
+options:
+  warmup-time: 1s
+  max-rounds: 10
+benchmarks:
+  - name: sleep
+    exec: sleep 0.1
+    env:
+      THREADS: "4"
+    timeout: 10s
+    matrix:
+      size: [1k, 1M]
+      threads: [1, 8]
+    options:
+      max-time: 2s
+"#;
+        assert_eq!(issues(content), vec![]);
+    }
+
+    #[test]
+    fn test_env_values_must_be_strings() {
+        let content = "benchmarks:\n  - exec: ./bench\n    env:\n      THREADS: 4\n";
+        assert_eq!(
+            issues(content),
+            vec![(
+                IssueSeverity::Error,
+                "benchmarks[0].env.THREADS".into(),
+                Some(4),
+                "expected string, found integer".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_invalid_types_are_located() {
+        let content = r#"
+options:
+  warmup-time: 1s
+  max-rounds: -3
+benchmarks:
+  - exec: sleep 0.1
+  - name: second
+    exec: [sleep, "0.1"]
+    options:
+      max_time: 2s
+"#;
+        assert_eq!(
+            issues(content),
+            vec![
+                (
+                    IssueSeverity::Error,
+                    "options.max-rounds".into(),
+                    Some(4),
+                    "must be greater than or equal to 0".into()
+                ),
+                (
+                    IssueSeverity::Error,
+                    "benchmarks[1].exec".into(),
+                    Some(8),
+                    "expected string or null, found array".into()
+                ),
+                (
+                    IssueSeverity::Warning,
+                    "benchmarks[1].options.max_time".into(),
+                    Some(10),
+                    "unknown key `max_time`, it will be ignored".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_has_location() {
+        let error = check_schema("options:\n  warmup-time: [1s\n").unwrap_err();
+        assert!(error.to_string().contains("line"), "{error}");
+    }
+}
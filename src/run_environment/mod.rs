@@ -14,6 +14,7 @@ use crate::prelude::*;
 
 pub use self::interfaces::*;
 pub use self::provider::RunEnvironmentProvider;
+pub(crate) use self::provider::get_commit_hash_default_impl;
 
 // RunEnvironment Provider implementations
 mod buildkite;
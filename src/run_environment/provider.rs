@@ -139,7 +139,7 @@ pub trait RunEnvironmentProvider {
     }
 }
 
-fn get_commit_hash_default_impl(repository_root_path: &str) -> Result<String> {
+pub(crate) fn get_commit_hash_default_impl(repository_root_path: &str) -> Result<String> {
     let repo = Repository::open(repository_root_path).context(format!(
         "Failed to open repository at path: {repository_root_path}"
     ))?;
@@ -1,4 +1,5 @@
 use clap::ValueEnum;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -7,7 +8,7 @@ mod shell_session;
 pub(crate) use shell_session::load_shell_session_mode;
 pub(crate) use shell_session::register_shell_session_mode;
 
-#[derive(ValueEnum, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(ValueEnum, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum RunnerMode {
     #[deprecated(note = "Use `RunnerMode::Simulation` instead")]
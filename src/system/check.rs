@@ -18,6 +18,16 @@ lazy_static! {
     };
 }
 
+/// Whether the system is part of the officially supported systems, other x86_64 and aarch64
+/// systems are supported on a best effort basis
+pub fn is_officially_supported(system_info: &SystemInfo) -> bool {
+    SUPPORTED_SYSTEMS.contains(&(
+        system_info.os.as_str(),
+        system_info.os_version.as_str(),
+        system_info.arch.as_str(),
+    ))
+}
+
 /// Checks if the provided system info is supported
 ///
 /// Supported systems:
@@ -28,13 +38,7 @@ lazy_static! {
 pub fn check_system(system_info: &SystemInfo) -> Result<()> {
     debug!("System info: {system_info:#?}");
 
-    let system_tuple = (
-        system_info.os.as_str(),
-        system_info.os_version.as_str(),
-        system_info.arch.as_str(),
-    );
-
-    if SUPPORTED_SYSTEMS.contains(&system_tuple) {
+    if is_officially_supported(system_info) {
         return Ok(());
     }
 
@@ -1,5 +1,5 @@
 mod check;
 mod info;
 
-pub use check::check_system;
+pub use check::{check_system, is_officially_supported};
 pub use info::SystemInfo;